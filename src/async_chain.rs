@@ -0,0 +1,166 @@
+use crate::eventchains::{ChainResult, EventContext, EventFailure, EventResult, FaultToleranceMode};
+use std::future::Future;
+use std::pin::Pin;
+
+/// Boxed future returned by `AsyncChainableEvent::execute`
+pub type EventFuture<'a> = Pin<Box<dyn Future<Output = EventResult<()>> + Send + 'a>>;
+
+/// Chainable event whose execution may await, e.g. to load a graph shard.
+/// Implementations typically return `Box::pin(async move { ... })`.
+pub trait AsyncChainableEvent: Send + Sync {
+    fn execute<'a>(&'a self, context: &'a mut EventContext) -> EventFuture<'a>;
+    fn name(&self) -> &str;
+
+    /// See `ChainableEvent::populate_defaults`
+    fn populate_defaults(&self, _context: &mut EventContext) {}
+}
+
+/// Sequential executor for `AsyncChainableEvent`s with the same fault
+/// tolerance semantics as `EventChain`. Middleware is not supported yet.
+pub struct AsyncEventChain {
+    events: Vec<Box<dyn AsyncChainableEvent>>,
+    fault_tolerance: FaultToleranceMode,
+}
+
+impl AsyncEventChain {
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            fault_tolerance: FaultToleranceMode::Strict,
+        }
+    }
+
+    pub fn with_fault_tolerance(mut self, mode: FaultToleranceMode) -> Self {
+        self.fault_tolerance = mode;
+        self
+    }
+
+    pub fn add_event(&mut self, event: Box<dyn AsyncChainableEvent>) -> &mut Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Await each event in order
+    pub async fn execute(&self, context: &mut EventContext) -> ChainResult {
+        let mut failures = Vec::new();
+
+        for event in &self.events {
+            context.enter_event(event.name());
+            let result = event.execute(context).await;
+            context.exit_event();
+
+            if let EventResult::Failure(message) | EventResult::Abort(message) = &result {
+                failures.push(EventFailure::new(event.name().to_string(), message.clone()));
+
+                if result.is_abort() {
+                    return ChainResult::aborted(failures);
+                }
+
+                match self.fault_tolerance {
+                    FaultToleranceMode::Strict => return ChainResult::failure(failures),
+                    FaultToleranceMode::Lenient => continue,
+                    FaultToleranceMode::BestEffort => {
+                        context.enter_event(event.name());
+                        event.populate_defaults(context);
+                        context.exit_event();
+                    }
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            ChainResult::success()
+        } else {
+            ChainResult::partial_success(failures)
+        }
+    }
+
+    /// Run `execute` to completion on a new single-threaded tokio runtime,
+    /// for callers that are not async themselves
+    pub fn execute_blocking(&self, context: &mut EventContext) -> ChainResult {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("failed to build tokio runtime")
+            .block_on(self.execute(context))
+    }
+}
+
+impl Default for AsyncEventChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eventchains::ChainStatus;
+    use std::time::{Duration, Instant};
+
+    /// Sleeps, then appends its index to the "order" key
+    struct SleepEvent(usize);
+
+    impl AsyncChainableEvent for SleepEvent {
+        fn execute<'a>(&'a self, context: &'a mut EventContext) -> EventFuture<'a> {
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                let mut order: Vec<usize> = context.get("order").unwrap_or_default();
+                order.push(self.0);
+                context.set("order", order);
+                EventResult::Success(())
+            })
+        }
+
+        fn name(&self) -> &str {
+            "Sleep"
+        }
+    }
+
+    struct FailingEvent;
+
+    impl AsyncChainableEvent for FailingEvent {
+        fn execute<'a>(&'a self, _context: &'a mut EventContext) -> EventFuture<'a> {
+            Box::pin(async { EventResult::Failure("shard unavailable".to_string()) })
+        }
+
+        fn name(&self) -> &str {
+            "Failing"
+        }
+    }
+
+    #[test]
+    fn events_are_awaited_in_order() {
+        let mut chain = AsyncEventChain::new();
+        chain.add_event(Box::new(SleepEvent(1)));
+        chain.add_event(Box::new(SleepEvent(2)));
+
+        let mut context = EventContext::new();
+        let start = Instant::now();
+        let result = chain.execute_blocking(&mut context);
+
+        assert_eq!(result.status, ChainStatus::Completed);
+        assert!(start.elapsed() >= Duration::from_millis(10));
+        assert_eq!(context.get::<Vec<usize>>("order"), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn strict_chain_stops_at_a_failed_await() {
+        let mut chain = AsyncEventChain::new();
+        chain.add_event(Box::new(FailingEvent));
+        chain.add_event(Box::new(SleepEvent(1)));
+
+        let mut context = EventContext::new();
+        let result = chain.execute_blocking(&mut context);
+        assert_eq!(result.status, ChainStatus::Failed);
+        assert_eq!(result.failures[0].error_message, "shard unavailable");
+        assert_eq!(context.get::<Vec<usize>>("order"), None);
+
+        let mut lenient = AsyncEventChain::new().with_fault_tolerance(FaultToleranceMode::Lenient);
+        lenient.add_event(Box::new(FailingEvent));
+        lenient.add_event(Box::new(SleepEvent(1)));
+        let result = lenient.execute_blocking(&mut context);
+        assert_eq!(result.status, ChainStatus::CompletedWithWarnings);
+        assert_eq!(context.get::<Vec<usize>>("order"), Some(vec![1]));
+    }
+}
@@ -0,0 +1,447 @@
+use crate::comprehensive_benchmarking::{compare_fingerprints, ComprehensiveMetrics, MemoryFingerprint};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Default allowed mean-duration regression for `--baseline`
+pub const DEFAULT_REGRESSION_THRESHOLD_PCT: f64 = 10.0;
+
+/// Fewest measured runs, on both sides, for a benchmark's duration to be
+/// gated against the baseline; below this a single slow run moves it
+pub const MIN_BASELINE_RUNS: usize = 10;
+
+/// Peak-memory drift allowed against a baseline. Allocation counts are
+/// deterministic for a fixed graph and run count, so they must match exactly.
+pub const PEAK_MEMORY_TOLERANCE_PCT: f64 = 1.0;
+
+/// Duration statistic `compare_to_baseline` gates on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GateStatistic {
+    #[default]
+    Mean,
+    /// Opt-in with `--gate-median`: one descheduled run cannot move it, but
+    /// neither can a regression confined to the slower half of the runs
+    Median,
+}
+
+impl GateStatistic {
+    fn nanos(self, entry: &BaselineEntry) -> u64 {
+        match self {
+            GateStatistic::Mean => entry.mean_nanos,
+            GateStatistic::Median => entry.median_nanos,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            GateStatistic::Mean => "Mean",
+            GateStatistic::Median => "Median",
+        }
+    }
+}
+
+/// Timing summary of one benchmark in one test case
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub test_case: String,
+    pub benchmark: String,
+    pub mean_nanos: u64,
+    pub median_nanos: u64,
+    pub p99_nanos: u64,
+    pub runs: usize,
+    /// Allocations over all measured runs; `None` when memory was not
+    /// tracked (e.g. `--parallel`) or in baselines saved before it was recorded
+    pub allocation_count: Option<usize>,
+    /// Peak bytes above those live when measurement started; `None` as for
+    /// `allocation_count`
+    pub peak_memory: Option<usize>,
+}
+
+impl BaselineEntry {
+    pub fn from_metrics(test_case: &str, benchmark: &str, metrics: &ComprehensiveMetrics) -> Self {
+        Self {
+            test_case: test_case.to_string(),
+            benchmark: benchmark.to_string(),
+            mean_nanos: metrics.mean_duration.as_nanos() as u64,
+            median_nanos: metrics.median_duration.as_nanos() as u64,
+            p99_nanos: metrics.p99_duration.as_nanos() as u64,
+            runs: metrics.runs,
+            allocation_count: metrics
+                .memory_tracked
+                .then_some(metrics.memory_stats.allocation_count),
+            peak_memory: metrics.memory_tracked.then_some(metrics.memory_stats.peak_memory),
+        }
+    }
+
+    /// Memory fingerprint named "<test case> / <benchmark>"; `None` unless
+    /// both memory fields were recorded
+    fn fingerprint(&self) -> Option<MemoryFingerprint> {
+        Some(MemoryFingerprint {
+            name: format!("{} / {}", self.test_case, self.benchmark),
+            allocation_count: self.allocation_count?,
+            peak_memory: self.peak_memory?,
+        })
+    }
+}
+
+/// Metrics of a whole run, as written by `--save-baseline`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BaselineFile {
+    pub entries: Vec<BaselineEntry>,
+    /// Measured with `--parallel`: test cases ran concurrently, so timings
+    /// include contention and memory was not tracked
+    #[serde(default)]
+    pub parallel: bool,
+}
+
+impl BaselineFile {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("cannot read baseline {}: {}", path.display(), err))?;
+        serde_json::from_str(&text)
+            .map_err(|err| format!("invalid baseline {}: {}", path.display(), err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|err| format!("cannot serialize baseline: {}", err))?;
+        fs::write(path, text)
+            .map_err(|err| format!("cannot write baseline {}: {}", path.display(), err))
+    }
+
+    /// Whether this run can be compared against `saved` at all. Parallel
+    /// runs time test cases while they compete for the CPU, so neither side
+    /// may have been measured with `--parallel`.
+    pub fn check_comparable(&self, saved: &BaselineFile) -> Result<(), String> {
+        match (saved.parallel, self.parallel) {
+            (false, false) => Ok(()),
+            (true, _) => Err(
+                "the baseline was saved with --parallel, so its timings include contention between \
+                 test cases; save it again without --parallel"
+                    .to_string(),
+            ),
+            (false, true) => Err(
+                "--baseline cannot be combined with --parallel: concurrent test cases skew the \
+                 timings compared"
+                    .to_string(),
+            ),
+        }
+    }
+
+    fn find(&self, test_case: &str, benchmark: &str) -> Option<&BaselineEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.test_case == test_case && e.benchmark == benchmark)
+    }
+}
+
+/// Change in the gated duration of one benchmark relative to the saved baseline
+#[derive(Debug, Clone)]
+pub struct RegressionDelta {
+    pub test_case: String,
+    pub benchmark: String,
+    pub baseline_nanos: u64,
+    pub current_nanos: u64,
+    pub delta_pct: f64,
+    /// Both sides have at least `MIN_BASELINE_RUNS` runs; otherwise the
+    /// delta is reported but never counts as a regression
+    pub gated: bool,
+    pub regressed: bool,
+}
+
+/// Compare the `statistic` duration of every benchmark in `current` against
+/// its counterpart in `baseline`. Benchmarks absent from the baseline are
+/// skipped.
+pub fn compare_to_baseline(
+    baseline: &BaselineFile,
+    current: &BaselineFile,
+    threshold_pct: f64,
+    statistic: GateStatistic,
+) -> Vec<RegressionDelta> {
+    current
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let saved = baseline.find(&entry.test_case, &entry.benchmark)?;
+            let (saved_nanos, current_nanos) = (statistic.nanos(saved), statistic.nanos(entry));
+            let delta_pct = if saved_nanos == 0 {
+                0.0
+            } else {
+                (current_nanos as f64 - saved_nanos as f64) / saved_nanos as f64 * 100.0
+            };
+            let gated = saved.runs >= MIN_BASELINE_RUNS && entry.runs >= MIN_BASELINE_RUNS;
+            Some(RegressionDelta {
+                test_case: entry.test_case.clone(),
+                benchmark: entry.benchmark.clone(),
+                baseline_nanos: saved_nanos,
+                current_nanos,
+                delta_pct,
+                gated,
+                regressed: gated && delta_pct > threshold_pct,
+            })
+        })
+        .collect()
+}
+
+/// Compare the memory fingerprint of every benchmark in `current` against
+/// `baseline`, returning one message per changed allocation count or peak
+/// drifting by more than `peak_tolerance_pct`. Benchmarks without memory
+/// stats on either side, or measured over a different number of runs, are
+/// skipped: their allocation counts are not comparable.
+pub fn compare_allocations(
+    baseline: &BaselineFile,
+    current: &BaselineFile,
+    peak_tolerance_pct: f64,
+) -> Vec<String> {
+    let (golden, measured): (Vec<MemoryFingerprint>, Vec<MemoryFingerprint>) = current
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let saved = baseline.find(&entry.test_case, &entry.benchmark)?;
+            if saved.runs != entry.runs {
+                return None;
+            }
+            Some((saved.fingerprint()?, entry.fingerprint()?))
+        })
+        .unzip();
+
+    compare_fingerprints(&measured, &golden, peak_tolerance_pct)
+}
+
+/// Print the deltas and return whether none regressed
+pub fn print_baseline_comparison(deltas: &[RegressionDelta], threshold_pct: f64, statistic: GateStatistic) -> bool {
+    println!("\n{}", "=".repeat(90).bright_magenta().bold());
+    println!(
+        "{}",
+        format!(
+            "Baseline Comparison - {} Duration (threshold {:.2}%)",
+            statistic.label(),
+            threshold_pct
+        )
+            .bright_magenta()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_magenta().bold());
+    println!(
+        "{:<25} {:<40} {:>10} {:>10} {:>10}",
+        "Test Case".bold(),
+        "Benchmark".bold(),
+        "Base (μs)".bold(),
+        "Now (μs)".bold(),
+        "Delta".bold()
+    );
+    println!("{}", "-".repeat(90));
+
+    for delta in deltas {
+        let change = format!("{:+.2}%", delta.delta_pct);
+        let change = if delta.regressed {
+            change.red().bold()
+        } else if delta.gated {
+            change.green()
+        } else {
+            change.dimmed()
+        };
+        println!(
+            "{:<25} {:<40} {:>10.2} {:>10.2} {:>10}",
+            delta.test_case,
+            delta.benchmark,
+            delta.baseline_nanos as f64 / 1000.0,
+            delta.current_nanos as f64 / 1000.0,
+            change
+        );
+    }
+
+    let ungated = deltas.iter().filter(|d| !d.gated).count();
+    if ungated > 0 {
+        println!(
+            "{}",
+            format!(
+                "  {} benchmarks have fewer than {} runs and are not gated (dimmed)",
+                ungated, MIN_BASELINE_RUNS
+            )
+            .dimmed()
+        );
+    }
+
+    let regressed = deltas.iter().filter(|d| d.regressed).count();
+    if regressed == 0 {
+        println!("\n{}", "No regressions against baseline".bright_green().bold());
+    } else {
+        println!(
+            "\n{}",
+            format!("{} of {} benchmarks regressed", regressed, deltas.len())
+                .bright_red()
+                .bold()
+        );
+    }
+
+    regressed == 0
+}
+
+/// Print the allocation changes found by `compare_allocations` and return
+/// whether there were none
+pub fn print_allocation_comparison(changes: &[String]) -> bool {
+    if changes.is_empty() {
+        println!("{}", "No allocation changes against baseline".bright_green().bold());
+        return true;
+    }
+
+    println!(
+        "\n{}",
+        format!("{} benchmarks changed their memory fingerprint:", changes.len())
+            .bright_red()
+            .bold()
+    );
+    for change in changes {
+        println!("  {}", change);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(benchmark: &str, mean_nanos: u64, allocation_count: usize) -> BaselineEntry {
+        BaselineEntry {
+            test_case: "100 nodes".to_string(),
+            benchmark: benchmark.to_string(),
+            mean_nanos,
+            median_nanos: mean_nanos,
+            p99_nanos: mean_nanos,
+            runs: 1000,
+            allocation_count: Some(allocation_count),
+            peak_memory: Some(4096),
+        }
+    }
+
+    fn file(entries: Vec<BaselineEntry>) -> BaselineFile {
+        BaselineFile {
+            entries,
+            parallel: false,
+        }
+    }
+
+    #[test]
+    fn allocation_change_is_detected_with_unchanged_timing() {
+        let saved = file(vec![entry("Tier 1", 5000, 5000)]);
+        let current = file(vec![entry("Tier 1", 5000, 6000)]);
+
+        let deltas = compare_to_baseline(&saved, &current, DEFAULT_REGRESSION_THRESHOLD_PCT, GateStatistic::Mean);
+        assert!(deltas.iter().all(|d| !d.regressed));
+
+        let changes = compare_allocations(&saved, &current, PEAK_MEMORY_TOLERANCE_PCT);
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].contains("allocation count changed from 5000 to 6000"));
+    }
+
+    #[test]
+    fn identical_fingerprints_pass() {
+        let saved = file(vec![entry("Tier 1", 5000, 5000)]);
+        let current = file(vec![entry("Tier 1", 7000, 5000)]);
+
+        assert!(compare_allocations(&saved, &current, PEAK_MEMORY_TOLERANCE_PCT).is_empty());
+    }
+
+    #[test]
+    fn untracked_or_differently_sized_runs_are_not_compared() {
+        let saved = file(vec![entry("Tier 1", 5000, 5000), entry("Tier 2", 5000, 5000)]);
+        let mut untracked = entry("Tier 1", 5000, 0);
+        untracked.allocation_count = None;
+        let mut shorter = entry("Tier 2", 5000, 50);
+        shorter.runs = 10;
+
+        let current = file(vec![untracked, shorter]);
+        assert!(compare_allocations(&saved, &current, PEAK_MEMORY_TOLERANCE_PCT).is_empty());
+    }
+
+    #[test]
+    fn regression_is_gated_on_the_mean_by_default() {
+        let saved = file(vec![entry("Tier 1", 5000, 5000)]);
+
+        // Slower runs drag the mean up 20% while the median holds
+        let mut slower = entry("Tier 1", 6000, 5000);
+        slower.median_nanos = 5100;
+        let deltas = compare_to_baseline(
+            &saved,
+            &file(vec![slower]),
+            DEFAULT_REGRESSION_THRESHOLD_PCT,
+            GateStatistic::default(),
+        );
+        assert!(deltas[0].regressed);
+        assert!((deltas[0].delta_pct - 20.0).abs() < 1e-9);
+
+        let faster = entry("Tier 1", 4000, 5000);
+        let deltas = compare_to_baseline(
+            &saved,
+            &file(vec![faster]),
+            DEFAULT_REGRESSION_THRESHOLD_PCT,
+            GateStatistic::default(),
+        );
+        assert!(!deltas[0].regressed);
+    }
+
+    #[test]
+    fn median_gate_ignores_a_single_slow_run() {
+        let saved = file(vec![entry("Tier 1", 5000, 5000)]);
+
+        // One slow run drags the mean up 50% while the median holds
+        let mut noisy = entry("Tier 1", 7500, 5000);
+        noisy.median_nanos = 5100;
+        let deltas = compare_to_baseline(
+            &saved,
+            &file(vec![noisy]),
+            DEFAULT_REGRESSION_THRESHOLD_PCT,
+            GateStatistic::Median,
+        );
+        assert!(!deltas[0].regressed);
+        assert!((deltas[0].delta_pct - 2.0).abs() < 1e-9);
+
+        let mut slower = entry("Tier 1", 5000, 5000);
+        slower.median_nanos = 6000;
+        let deltas = compare_to_baseline(
+            &saved,
+            &file(vec![slower]),
+            DEFAULT_REGRESSION_THRESHOLD_PCT,
+            GateStatistic::Median,
+        );
+        assert!(deltas[0].regressed);
+    }
+
+    #[test]
+    fn too_few_runs_are_reported_but_not_gated() {
+        let mut saved = entry("Tier 1", 5000, 5000);
+        saved.runs = MIN_BASELINE_RUNS - 1;
+        let mut current = entry("Tier 1", 9000, 5000);
+        current.runs = MIN_BASELINE_RUNS - 1;
+
+        let deltas = compare_to_baseline(
+            &file(vec![saved]),
+            &file(vec![current]),
+            DEFAULT_REGRESSION_THRESHOLD_PCT,
+            GateStatistic::Mean,
+        );
+        assert_eq!(deltas.len(), 1);
+        assert!(!deltas[0].gated);
+        assert!(!deltas[0].regressed);
+    }
+
+    #[test]
+    fn parallel_runs_are_not_comparable() {
+        let sequential = file(Vec::new());
+        let mut parallel = file(Vec::new());
+        parallel.parallel = true;
+
+        assert!(sequential.check_comparable(&sequential).is_ok());
+        assert!(sequential.check_comparable(&parallel).is_err());
+        assert!(parallel.check_comparable(&sequential).is_err());
+        assert!(parallel.check_comparable(&parallel).is_err());
+    }
+
+    #[test]
+    fn baselines_without_the_parallel_flag_load_as_sequential() {
+        let saved: BaselineFile = serde_json::from_str(r#"{"entries": []}"#).unwrap();
+        assert!(!saved.parallel);
+    }
+}
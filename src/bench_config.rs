@@ -0,0 +1,133 @@
+use crate::comprehensive_benchmarking::DEFAULT_WARMUP_RUNS;
+use crate::graph::DEFAULT_SEED;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Measured runs per benchmark when a config omits `runs`
+pub const DEFAULT_BENCH_RUNS: usize = 30;
+
+/// One test case: the graph to generate and how to sample each benchmark on
+/// it. In a config file only `nodes` and `edges` are required, e.g.
+/// `[{"nodes": 100, "edges": 500}, {"nodes": 1000, "edges": 5000, "runs": 10}]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BenchConfig {
+    pub nodes: usize,
+    pub edges: usize,
+    #[serde(default = "default_runs")]
+    pub runs: usize,
+    /// Untimed runs before each benchmark is measured
+    #[serde(default = "default_warmup")]
+    pub warmup: usize,
+    /// Seed of the generated graph
+    #[serde(default = "default_seed")]
+    pub seed: u64,
+}
+
+fn default_runs() -> usize {
+    DEFAULT_BENCH_RUNS
+}
+
+fn default_warmup() -> usize {
+    DEFAULT_WARMUP_RUNS
+}
+
+fn default_seed() -> u64 {
+    DEFAULT_SEED
+}
+
+impl BenchConfig {
+    pub fn new(nodes: usize, edges: usize, runs: usize) -> Self {
+        Self {
+            nodes,
+            edges,
+            runs,
+            warmup: DEFAULT_WARMUP_RUNS,
+            seed: DEFAULT_SEED,
+        }
+    }
+
+    /// The test cases run when no config file is given
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new(100, 500, 100),   // Small graph - more runs
+            Self::new(500, 2500, 50),   // Medium graph
+            Self::new(1000, 5000, 30),  // Large graph
+            Self::new(2000, 10000, 20), // Extra large graph
+        ]
+    }
+
+    /// Read a JSON array of configs, as passed to `--config`
+    pub fn load_all(path: &Path) -> Result<Vec<Self>, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("cannot read config {}: {}", path.display(), err))?;
+        let configs: Vec<Self> = serde_json::from_str(&text)
+            .map_err(|err| format!("invalid config {}: {}", path.display(), err))?;
+
+        if configs.is_empty() {
+            return Err(format!("config {} has no test cases", path.display()));
+        }
+        for config in &configs {
+            if config.nodes == 0 || config.runs == 0 {
+                return Err(format!(
+                    "config {}: each test case needs at least 1 node and 1 run",
+                    path.display()
+                ));
+            }
+        }
+        Ok(configs)
+    }
+
+    /// Stable name of the test case, as used in saved baselines
+    pub fn label(&self) -> String {
+        format!("{} nodes, {} edges", self.nodes, self.edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+    /// Loads `json` through a temp file of its own, so tests running in
+    /// parallel don't overwrite each other's config
+    fn load(json: &str) -> Result<Vec<BenchConfig>, String> {
+        let file = NEXT_FILE.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("bench-config-{}-{}.json", std::process::id(), file));
+        fs::write(&path, json).unwrap();
+        let configs = BenchConfig::load_all(&path);
+        fs::remove_file(&path).unwrap();
+        configs
+    }
+
+    #[test]
+    fn omitted_fields_take_the_defaults() {
+        let configs = load(r#"[{"nodes": 100, "edges": 500}, {"nodes": 10, "edges": 20, "runs": 5, "warmup": 0, "seed": 7}]"#)
+            .unwrap();
+
+        assert_eq!(configs[0], BenchConfig::new(100, 500, DEFAULT_BENCH_RUNS));
+        assert_eq!(configs[0].warmup, DEFAULT_WARMUP_RUNS);
+        assert_eq!(configs[0].seed, DEFAULT_SEED);
+        assert_eq!(
+            configs[1],
+            BenchConfig {
+                nodes: 10,
+                edges: 20,
+                runs: 5,
+                warmup: 0,
+                seed: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        assert!(load("[]").unwrap_err().contains("no test cases"));
+        assert!(load(r#"[{"nodes": 10, "edges": 20, "runs": 0}]"#).is_err());
+        assert!(load(r#"[{"nodes": 10}]"#).is_err());
+        assert!(load(r#"[{"nodes": 10, "edges": 20, "rounds": 3}]"#).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::comprehensive_benchmarking::{MIDDLEWARE_THRESHOLDS, TIMING_THRESHOLDS};
 use colored::*;
 use std::time::{Duration, Instant};
 
@@ -13,14 +14,26 @@ pub struct BenchmarkStats {
 }
 
 impl BenchmarkStats {
+    /// Statistics over `durations`; all zero when there are none
     pub fn from_durations(mut durations: Vec<Duration>) -> Self {
+        if durations.is_empty() {
+            return Self {
+                mean_duration: Duration::ZERO,
+                median_duration: Duration::ZERO,
+                min_duration: Duration::ZERO,
+                max_duration: Duration::ZERO,
+                std_dev_nanos: 0.0,
+                runs: 0,
+            };
+        }
+
         durations.sort();
         let runs = durations.len();
 
         let mean_nanos = durations.iter().map(|d| d.as_nanos() as f64).sum::<f64>() / runs as f64;
         let mean_duration = Duration::from_nanos(mean_nanos as u64);
 
-        let median_duration = if runs % 2 == 0 {
+        let median_duration = if runs.is_multiple_of(2) {
             let mid = runs / 2;
             Duration::from_nanos(
                 ((durations[mid - 1].as_nanos() + durations[mid].as_nanos()) / 2) as u64,
@@ -97,6 +110,7 @@ impl Tier1Results {
                 .bold()
         );
         println!("{}", "=".repeat(80).bright_cyan().bold());
+        println!("{}", TIMING_THRESHOLDS.legend("Timing overhead").dimmed());
         println!("\nComparison:");
         println!("   Baseline: 3 direct function calls (no error handling, no tracking)");
         println!("   EventChains: Full pattern with 0 middleware");
@@ -121,13 +135,7 @@ impl Tier1Results {
         );
 
         let overhead = self.eventchains_no_middleware.overhead_vs(&self.bare_functions);
-        let color = if overhead < 20.0 {
-            "green"
-        } else if overhead < 50.0 {
-            "yellow"
-        } else {
-            "red"
-        };
+        let color = TIMING_THRESHOLDS.color_for(overhead);
 
         println!(
             "{:<40} {:>12.2} {:>12.2} {:>12}",
@@ -167,6 +175,7 @@ impl Tier2Results {
                 .bold()
         );
         println!("{}", "=".repeat(80).bright_cyan().bold());
+        println!("{}", TIMING_THRESHOLDS.legend("Timing overhead").dimmed());
         println!("\nComparison:");
         println!("   Baseline: Manual implementation with error handling, name tracking, context");
         println!("   EventChains: Full pattern with 0 middleware");
@@ -191,13 +200,7 @@ impl Tier2Results {
         );
 
         let overhead = self.eventchains_no_middleware.overhead_vs(&self.manual_instrumented);
-        let color = if overhead < 15.0 {
-            "green"
-        } else if overhead < 30.0 {
-            "yellow"
-        } else {
-            "red"
-        };
+        let color = TIMING_THRESHOLDS.color_for(overhead);
 
         println!(
             "{:<40} {:>12.2} {:>12.2} {:>12}",
@@ -240,6 +243,7 @@ impl Tier3Results {
                 .bold()
         );
         println!("{}", "=".repeat(80).bright_cyan().bold());
+        println!("{}", MIDDLEWARE_THRESHOLDS.legend("Middleware overhead").dimmed());
         println!("\nComparison:");
         println!("   EventChains with 0, 1, 3, 5, and 10 middleware layers");
         println!("   Metric: Incremental cost per middleware layer\n");
@@ -273,13 +277,7 @@ impl Tier3Results {
                 );
             } else {
                 let overhead = stats.overhead_vs(&self.no_middleware);
-                let color = if overhead < 30.0 {
-                    "green"
-                } else if overhead < 60.0 {
-                    "yellow"
-                } else {
-                    "red"
-                };
+                let color = MIDDLEWARE_THRESHOLDS.color_for(overhead);
                 println!(
                     "{:<40} {:>12.2} {:>12.2} {:>12}",
                     name,
@@ -330,6 +328,7 @@ impl Tier4Results {
                 .bold()
         );
         println!("{}", "=".repeat(80).bright_cyan().bold());
+        println!("{}", TIMING_THRESHOLDS.legend("Timing overhead").dimmed());
         println!("\nComparison:");
         println!("   Baseline: Manual implementation with logging, timing, error handling");
         println!("   EventChains: Optimized version (4 events) with logging + timing middleware");
@@ -359,13 +358,7 @@ impl Tier4Results {
         let overhead = self.eventchains_with_logging_timing
             .overhead_vs(&self.manual_with_logging_timing);
 
-        let color = if overhead.abs() < 10.0 {
-            "green"
-        } else if overhead.abs() < 25.0 {
-            "yellow"
-        } else {
-            "red"
-        };
+        let color = TIMING_THRESHOLDS.color_for(overhead.abs());
 
         let sign = if diff >= 0.0 { "+" } else { "" };
         println!(
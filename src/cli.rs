@@ -0,0 +1,288 @@
+use crate::baseline::{GateStatistic, DEFAULT_REGRESSION_THRESHOLD_PCT};
+use crate::comprehensive_benchmarking::BenchmarkOptions;
+use std::path::PathBuf;
+
+/// Command-line options for the benchmark binary
+#[derive(Debug, Clone)]
+pub struct CliOptions {
+    /// Exit nonzero if any tier's CPU overhead exceeds this percentage
+    pub fail_on_regression: Option<f64>,
+    /// Run test cases concurrently, without memory tracking
+    pub parallel: bool,
+    /// Saved metrics to compare this run against
+    pub baseline: Option<PathBuf>,
+    /// Where to write this run's metrics
+    pub save_baseline: Option<PathBuf>,
+    /// Allowed regression against `baseline`
+    pub threshold_percent: f64,
+    /// Duration statistic compared against `baseline`
+    pub gate_statistic: GateStatistic,
+    /// Where to stream per-run records of the long-running benchmark
+    pub stream_jsonl: Option<PathBuf>,
+    /// Print without ANSI colors
+    pub no_color: bool,
+    /// JSON list of test cases to run instead of the built-in ones
+    pub config: Option<PathBuf>,
+    /// Directory to write criterion-compatible results into
+    pub criterion_out: Option<PathBuf>,
+    /// Seeds to cross-check every implementation over, instead of benchmarking
+    pub cross_check: Option<u64>,
+    /// DIMACS `.gr` graph to benchmark instead of generated graphs
+    pub dimacs: Option<PathBuf>,
+    /// Untimed runs before each benchmark, overriding every test case's own
+    pub warmup: Option<usize>,
+    /// Percentiles reported for every benchmark instead of P95 and P99
+    pub percentiles: Option<Vec<f64>>,
+    /// Percentage of samples dropped from each tail of every benchmark
+    pub trim_percent: f64,
+    pub help: bool,
+}
+
+pub const USAGE: &str = "\
+Usage: dijkstra_eventchains [OPTIONS]
+
+Options:
+  --fail-on-regression <pct>  Print per-tier verdicts and exit with status 1 if
+                              any tier's overhead exceeds <pct> percent or any
+                              benchmark has a success rate below 100%
+  --parallel                  Run test cases on separate threads; faster, but
+                              memory tracking is disabled
+  --baseline <file>           Compare mean durations and allocation counts
+                              against a file written by --save-baseline; exit
+                              with status 1 on a regression or any change in a
+                              benchmark's allocation count. Benchmarks with
+                              fewer than 10 runs are not gated; neither run
+                              may use --parallel
+  --save-baseline <file>      Write this run's metrics as JSON
+  --threshold-percent <pct>   Allowed regression for --baseline (default 10)
+  --gate-median               Compare median durations for --baseline instead
+                              of means, so one descheduled run cannot fail it
+  --stream-jsonl <file>       Also run a 100k-run benchmark, writing each run to
+                              <file> as a JSON line as soon as it finishes
+  --no-color                  Print without colors (also set by a non-empty
+                              NO_COLOR environment variable)
+  --config <file>             Run the test cases in <file>, a JSON array of
+                              {\"nodes\", \"edges\", \"runs\", \"warmup\", \"seed\"}
+                              objects; only nodes and edges are required
+  --dimacs <file>             Benchmark the DIMACS shortest-path graph in <file>
+                              (e.g. a 9th DIMACS Challenge road network)
+                              instead of generated graphs, from node 1 to the
+                              last node; cannot be combined with --config
+  --criterion-out <dir>       Also write every benchmark in criterion's
+                              target/criterion layout under <dir>
+  --cross-check <seeds>       Instead of benchmarking, check that every Dijkstra
+                              implementation agrees on random graphs from
+                              <seeds> seeds; exit with status 1 on divergence
+  --trim <pct>                Drop the fastest and slowest <pct> percent of each
+                              benchmark's runs before computing statistics
+                              (default 0, below 50)
+  --warmup <runs>             Untimed runs before each benchmark is measured,
+                              overriding the warmup of every test case
+                              (default 1; 0 disables warmup)
+  --percentiles <list>        Comma-separated percentiles to report for each
+                              benchmark, e.g. 50,90,99.9 (default 95,99)
+  -h, --help                  Print this help";
+
+impl Default for CliOptions {
+    fn default() -> Self {
+        Self {
+            fail_on_regression: None,
+            parallel: false,
+            baseline: None,
+            save_baseline: None,
+            threshold_percent: DEFAULT_REGRESSION_THRESHOLD_PCT,
+            gate_statistic: GateStatistic::Mean,
+            stream_jsonl: None,
+            no_color: false,
+            config: None,
+            criterion_out: None,
+            cross_check: None,
+            dimacs: None,
+            warmup: None,
+            percentiles: None,
+            trim_percent: 0.0,
+            help: false,
+        }
+    }
+}
+
+impl CliOptions {
+    /// Parse arguments, excluding the program name
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--fail-on-regression" => {
+                    options.fail_on_regression = Some(parse_percent(&arg, args.next())?);
+                }
+                "--parallel" => options.parallel = true,
+                "--baseline" => options.baseline = Some(parse_path(&arg, args.next())?),
+                "--save-baseline" => {
+                    options.save_baseline = Some(parse_path(&arg, args.next())?);
+                }
+                "--threshold-percent" => {
+                    options.threshold_percent = parse_percent(&arg, args.next())?;
+                }
+                "--gate-median" => options.gate_statistic = GateStatistic::Median,
+                "--stream-jsonl" => {
+                    options.stream_jsonl = Some(parse_path(&arg, args.next())?);
+                }
+                "--no-color" => options.no_color = true,
+                "--config" => options.config = Some(parse_path(&arg, args.next())?),
+                "--dimacs" => options.dimacs = Some(parse_path(&arg, args.next())?),
+                "--criterion-out" => {
+                    options.criterion_out = Some(parse_path(&arg, args.next())?);
+                }
+                "--cross-check" => {
+                    options.cross_check = Some(parse_count(&arg, args.next())?);
+                }
+                "--trim" => {
+                    let pct = parse_percent(&arg, args.next())?;
+                    if pct >= 50.0 {
+                        return Err(format!("{} must be below 50, got {}", arg, pct));
+                    }
+                    options.trim_percent = pct;
+                }
+                "--warmup" => options.warmup = Some(parse_runs(&arg, args.next())?),
+                "--percentiles" => {
+                    options.percentiles = Some(parse_percentiles(&arg, args.next())?);
+                }
+                "-h" | "--help" => options.help = true,
+                other => return Err(format!("unknown argument '{}'", other)),
+            }
+        }
+
+        if options.dimacs.is_some() && options.config.is_some() {
+            return Err("--dimacs and --config cannot be combined".to_string());
+        }
+
+        Ok(options)
+    }
+
+    /// Benchmark defaults selected on the command line, before any per-test
+    /// case settings
+    pub fn benchmark_options(&self) -> BenchmarkOptions {
+        let mut options = BenchmarkOptions::new().with_trim(self.trim_percent);
+        if let Some(percentiles) = &self.percentiles {
+            options = options.with_percentiles(percentiles);
+        }
+        options
+    }
+}
+
+/// True when the `NO_COLOR` convention (https://no-color.org) asks for
+/// uncolored output: the variable is set and not empty
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+fn parse_path(flag: &str, value: Option<String>) -> Result<PathBuf, String> {
+    value
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("{} requires a file path", flag))
+}
+
+fn parse_count(flag: &str, value: Option<String>) -> Result<u64, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    match value.parse() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("{} expects a positive integer, got '{}'", flag, value)),
+    }
+}
+
+fn parse_runs(flag: &str, value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a non-negative integer, got '{}'", flag, value))
+}
+
+fn parse_percentiles(flag: &str, value: Option<String>) -> Result<Vec<f64>, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    value
+        .split(',')
+        .map(|pct| {
+            let pct = parse_percent(flag, Some(pct.trim().to_string()))?;
+            if pct > 100.0 {
+                return Err(format!("{} must be at most 100, got {}", flag, pct));
+            }
+            Ok(pct)
+        })
+        .collect()
+}
+
+fn parse_percent(flag: &str, value: Option<String>) -> Result<f64, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    let pct: f64 = value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got '{}'", flag, value))?;
+    if !pct.is_finite() || pct < 0.0 {
+        return Err(format!(
+            "{} must be a non-negative percentage, got '{}'",
+            flag, value
+        ));
+    }
+    Ok(pct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliOptions, String> {
+        CliOptions::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn trim_sets_the_benchmark_defaults() {
+        let options = parse(&["--trim", "5"]).unwrap();
+        assert_eq!(options.benchmark_options().trim.trim_percent, 5.0);
+        assert_eq!(parse(&[]).unwrap().benchmark_options().trim.trim_percent, 0.0);
+        assert!(parse(&["--trim", "50"]).is_err());
+        assert!(parse(&["--trim"]).is_err());
+    }
+
+    #[test]
+    fn dimacs_replaces_the_configured_test_cases() {
+        let options = parse(&["--dimacs", "USA-road-d.NY.gr"]).unwrap();
+        assert_eq!(options.dimacs, Some(PathBuf::from("USA-road-d.NY.gr")));
+        assert!(parse(&["--dimacs"]).is_err());
+        assert!(parse(&["--dimacs", "a.gr", "--config", "cases.json"]).is_err());
+    }
+
+    #[test]
+    fn baseline_gates_on_the_mean_unless_median_is_requested() {
+        assert_eq!(parse(&[]).unwrap().gate_statistic, GateStatistic::Mean);
+        assert_eq!(parse(&["--gate-median"]).unwrap().gate_statistic, GateStatistic::Median);
+    }
+
+    #[test]
+    fn warmup_accepts_zero_but_not_a_negative_count() {
+        assert_eq!(parse(&["--warmup", "5"]).unwrap().warmup, Some(5));
+        assert_eq!(parse(&["--warmup", "0"]).unwrap().warmup, Some(0));
+        assert_eq!(parse(&[]).unwrap().warmup, None);
+        assert!(parse(&["--warmup", "-1"]).is_err());
+        assert!(parse(&["--warmup"]).is_err());
+    }
+
+    #[test]
+    fn percentiles_replace_the_defaults() {
+        let options = parse(&["--percentiles", "50, 90,99.9"]).unwrap();
+        assert_eq!(options.benchmark_options().percentiles, [50.0, 90.0, 99.9]);
+        assert_eq!(parse(&[]).unwrap().benchmark_options().percentiles, [95.0, 99.0]);
+        assert!(parse(&["--percentiles", "50,101"]).is_err());
+        assert!(parse(&["--percentiles", "50,,90"]).is_err());
+        assert!(parse(&["--percentiles"]).is_err());
+    }
+
+    #[test]
+    fn no_color_is_off_unless_requested() {
+        // The reports themselves are checked for escape sequences by running
+        // the binary (tests/no_color.rs), which keeps colored's global
+        // override out of this process
+        assert!(parse(&["--no-color"]).unwrap().no_color);
+        assert!(!parse(&[]).unwrap().no_color);
+    }
+}
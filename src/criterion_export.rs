@@ -0,0 +1,206 @@
+use crate::comprehensive_benchmarking::ComprehensiveMetrics;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ============================================================================
+// Criterion-Compatible Output
+// ============================================================================
+//
+// Writes `<dir>/<group>/<benchmark>/new/{benchmark,estimates,sample,tukey}.json`
+// in the layout criterion keeps under `target/criterion`, so its HTML report
+// generator can plot these benchmarks alongside existing ones. Every run is
+// one sample of one iteration. Criterion bootstraps its confidence intervals;
+// here they are normal approximations from the retained (trimmed) durations.
+
+const CONFIDENCE_LEVEL: f64 = 0.95;
+// Two-sided z-score for CONFIDENCE_LEVEL
+const Z_95: f64 = 1.959964;
+// Scales the median absolute deviation to estimate a normal std dev, as
+// criterion does
+const MAD_SCALE: f64 = 1.4826;
+
+#[derive(Serialize)]
+struct BenchmarkId {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+    throughput: Option<()>,
+    full_id: String,
+    directory_name: String,
+    title: String,
+}
+
+#[derive(Serialize)]
+struct ConfidenceInterval {
+    confidence_level: f64,
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+#[derive(Serialize)]
+struct Estimate {
+    confidence_interval: ConfidenceInterval,
+    point_estimate: f64,
+    standard_error: f64,
+}
+
+impl Estimate {
+    fn new(point_estimate: f64, standard_error: f64) -> Self {
+        Self {
+            confidence_interval: ConfidenceInterval {
+                confidence_level: CONFIDENCE_LEVEL,
+                lower_bound: point_estimate - Z_95 * standard_error,
+                upper_bound: point_estimate + Z_95 * standard_error,
+            },
+            point_estimate,
+            standard_error,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Estimates {
+    mean: Estimate,
+    median: Estimate,
+    median_abs_dev: Estimate,
+    slope: Option<Estimate>,
+    std_dev: Estimate,
+}
+
+#[derive(Serialize)]
+struct Sample {
+    sampling_mode: &'static str,
+    iters: Vec<f64>,
+    times: Vec<f64>,
+}
+
+/// Replace the characters criterion strips from directory names
+fn directory_safe(name: &str) -> String {
+    name.chars()
+        .map(|c| if "?\"/\\*<>:|^".contains(c) { '_' } else { c })
+        .collect()
+}
+
+/// Value at `fraction` of the way through ascending `sorted`, interpolating
+/// linearly between neighbors
+fn quantile(sorted: &[f64], fraction: f64) -> f64 {
+    let position = fraction * (sorted.len() - 1) as f64;
+    let (low, high) = (position.floor() as usize, position.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (position - low as f64)
+}
+
+/// Write one benchmark's results under `dir` in criterion's layout,
+/// returning the `new/` directory written. Fails without writing anything
+/// if `metrics` retained no samples.
+pub fn write_criterion_benchmark(
+    dir: &Path,
+    group: &str,
+    benchmark: &str,
+    metrics: &ComprehensiveMetrics,
+) -> Result<PathBuf, String> {
+    let times: Vec<f64> = metrics
+        .retained_durations
+        .iter()
+        .map(|d| d.as_nanos() as f64)
+        .collect();
+    if times.is_empty() {
+        return Err(format!("{} / {}: no samples to export", group, benchmark));
+    }
+
+    let n = times.len() as f64;
+    let mean = times.iter().sum::<f64>() / n;
+    let std_dev = (times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let median = quantile(&times, 0.5);
+
+    let mut deviations: Vec<f64> = times.iter().map(|t| (t - median).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    let median_abs_dev = quantile(&deviations, 0.5) * MAD_SCALE;
+
+    let mean_error = std_dev / n.sqrt();
+    let estimates = Estimates {
+        mean: Estimate::new(mean, mean_error),
+        // Asymptotic standard error of the median for normal data
+        median: Estimate::new(median, mean_error * (std::f64::consts::PI / 2.0).sqrt()),
+        median_abs_dev: Estimate::new(median_abs_dev, mean_error),
+        slope: None,
+        std_dev: Estimate::new(std_dev, std_dev / (2.0 * (n - 1.0).max(1.0)).sqrt()),
+    };
+
+    let (q1, q3) = (quantile(&times, 0.25), quantile(&times, 0.75));
+    let iqr = q3 - q1;
+    let tukey = [q1 - 3.0 * iqr, q1 - 1.5 * iqr, q3 + 1.5 * iqr, q3 + 3.0 * iqr];
+
+    let directory_name = format!("{}/{}", directory_safe(group), directory_safe(benchmark));
+    let full_id = format!("{}/{}", group, benchmark);
+    let id = BenchmarkId {
+        group_id: group.to_string(),
+        function_id: Some(benchmark.to_string()),
+        value_str: None,
+        throughput: None,
+        full_id: full_id.clone(),
+        directory_name: directory_name.clone(),
+        title: full_id,
+    };
+    let sample = Sample {
+        sampling_mode: "Flat",
+        iters: vec![1.0; times.len()],
+        times,
+    };
+
+    let out = dir.join(&directory_name).join("new");
+    fs::create_dir_all(&out)
+        .map_err(|err| format!("cannot create {}: {}", out.display(), err))?;
+    write_json(&out.join("benchmark.json"), &id)?;
+    write_json(&out.join("estimates.json"), &estimates)?;
+    write_json(&out.join("sample.json"), &sample)?;
+    write_json(&out.join("tukey.json"), &tukey)?;
+    Ok(out)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let text = serde_json::to_string(value)
+        .map_err(|err| format!("cannot serialize {}: {}", path.display(), err))?;
+    fs::write(path, text).map_err(|err| format!("cannot write {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comprehensive_benchmarking::MemoryStats;
+    use std::time::Duration;
+
+    #[test]
+    fn estimates_have_numeric_mean_median_and_std_dev() {
+        let durations = [10, 12, 11, 30, 12].iter().map(|&us| Duration::from_micros(us)).collect();
+        let metrics = ComprehensiveMetrics::from_runs(durations, MemoryStats::default(), 5);
+        let dir = std::env::temp_dir().join(format!("criterion-out-{}", std::process::id()));
+
+        let out = write_criterion_benchmark(&dir, "Tier 1", "baseline/100 nodes", &metrics).unwrap();
+        let text = fs::read_to_string(out.join("estimates.json")).unwrap();
+        let sample = fs::read_to_string(out.join("sample.json")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(out, dir.join("Tier 1/baseline_100 nodes/new"));
+        let estimates: serde_json::Value = serde_json::from_str(&text).unwrap();
+        for key in ["mean", "median", "std_dev"] {
+            let estimate = &estimates[key];
+            assert!(estimate["point_estimate"].is_f64(), "{}: {}", key, estimate);
+            assert!(estimate["standard_error"].is_f64(), "{}: {}", key, estimate);
+            assert!(estimate["confidence_interval"]["lower_bound"].is_f64(), "{}: {}", key, estimate);
+        }
+        assert_eq!(estimates["mean"]["point_estimate"], 15_000.0);
+        assert_eq!(estimates["median"]["point_estimate"], 12_000.0);
+
+        let sample: serde_json::Value = serde_json::from_str(&sample).unwrap();
+        assert_eq!(sample["times"].as_array().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn metrics_without_samples_write_nothing() {
+        let metrics = ComprehensiveMetrics::from_runs(Vec::new(), MemoryStats::default(), 0);
+        let dir = std::env::temp_dir().join(format!("criterion-empty-{}", std::process::id()));
+        assert!(write_criterion_benchmark(&dir, "Tier 1", "baseline", &metrics).is_err());
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::dijkstra_eventchains::*;
+use crate::dijkstra_traditional::{dijkstra_traditional, dijkstra_traditional_logged};
+use crate::fixed_state::dijkstra_fixed_baseline;
+use crate::graph::{CsrGraph, Graph, NodeId, ShortestPathResult, SimpleRng};
+use crate::hugepages::dijkstra_hugepage_baseline;
+use crate::k_shortest::k_shortest_paths;
+use crate::priority_queues::{dijkstra_btreeset_baseline, dijkstra_indexed_heap_baseline};
+use crate::tier_baselines::*;
+use crate::visitor::{dijkstra_with_visitor, DijkstraVisitor};
+use std::fmt;
+use std::sync::Arc;
+
+// ============================================================================
+// Randomized Cross-Check of Every Implementation
+// ============================================================================
+//
+// Runs every Dijkstra variant on the same random graph and query and checks
+// that each finds the Tier 1 baseline's distance along a valid path. Paths
+// may differ between variants when several share the minimum cost.
+
+/// `(nodes, edges)` of the graphs generated for each seed, from degenerate
+/// to sparse to dense
+pub const CROSS_CHECK_SIZES: [(usize, usize); 5] = [(1, 0), (2, 1), (12, 30), (60, 180), (250, 2000)];
+
+/// Capacity of the fixed-size state checked; covers every size above
+const FIXED_STATE_CAPACITY: usize = 256;
+
+type PathVariant = fn(Arc<Graph>, NodeId, NodeId) -> ShortestPathResult;
+type DistanceVariant = fn(Arc<Graph>, NodeId, NodeId) -> Option<u32>;
+
+/// Implementations returning a path, checked against `dijkstra_tier1_baseline`
+const PATH_VARIANTS: [(&str, PathVariant); 24] = [
+    ("Traditional", dijkstra_traditional),
+    ("Traditional logged (quiet)", |g, s, t| dijkstra_traditional_logged(g, s, t, false)),
+    ("Tier 1 result-wrapped baseline", dijkstra_result_wrapped_baseline),
+    ("Overflow-checked baseline", dijkstra_overflow_checked),
+    ("Tier 2 baseline", |g, s, t| dijkstra_tier2_baseline(g, s, t).unwrap_or_else(|err| panic!("{}", err))),
+    ("Tier 4 baseline", |g, s, t| dijkstra_tier4_baseline(g, s, t, false).0),
+    ("CSR baseline", |g, s, t| dijkstra_csr_baseline(Arc::new(CsrGraph::from(&*g)), s, t)),
+    ("Frozen graph", |g, s, t| dijkstra_frozen_baseline(Arc::new(Arc::unwrap_or_clone(g).freeze()), s, t)),
+    ("Multi-source baseline (one source)", |g, s, t| dijkstra_multi_source_baseline(g, &[s], t)),
+    ("Bidirectional baseline", dijkstra_bidirectional_baseline),
+    ("BTreeSet queue", dijkstra_btreeset_baseline),
+    ("Indexed heap queue", dijkstra_indexed_heap_baseline),
+    ("Huge-page state", dijkstra_hugepage_baseline),
+    ("Fixed-size state", |g, s, t| dijkstra_fixed_baseline::<FIXED_STATE_CAPACITY>(&g, s, t).to_result(s, t)),
+    ("Visitor search", |g, s, t| dijkstra_with_visitor(g, s, t, &mut NoopVisitor)),
+    ("k-shortest (k = 1)", |g, s, t| {
+        k_shortest_paths(g, s, t, 1)
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| ShortestPathResult::unreachable(s, t))
+    }),
+    ("EventChains bare", dijkstra_eventchains_bare),
+    ("EventChains batched (16)", |g, s, t| dijkstra_eventchains_batched(g, s, t, 16)),
+    ("EventChains full", |g, s, t| dijkstra_eventchains_full(g, s, t, false).0),
+    ("EventChains optimized", dijkstra_eventchains_optimized),
+    ("EventChains static dispatch", dijkstra_eventchains_static),
+    ("EventChains dyn loop", dijkstra_eventchains_dyn_loop),
+    ("EventChains with middleware", |g, s, t| dijkstra_eventchains_optimized_with_middleware(g, s, t, false)),
+    ("EventChains 5 middleware", |g, s, t| dijkstra_eventchains_with_n_middleware(g, s, t, 5)),
+];
+
+/// Implementations returning only a distance
+const DISTANCE_VARIANTS: [(&str, DistanceVariant); 3] = [
+    ("Distance-only baseline", dijkstra_distance_only_baseline),
+    ("EventChains distance-only", dijkstra_eventchains_distance_only),
+    ("EventChains all distances", |g, s, t| dijkstra_eventchains_all_distances(g, s).distances[t.0]),
+];
+
+struct NoopVisitor;
+
+impl DijkstraVisitor for NoopVisitor {}
+
+/// First disagreement found by `cross_check`, with what is needed to
+/// reproduce it
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub seed: u64,
+    pub nodes: usize,
+    pub edges: usize,
+    pub source: NodeId,
+    pub target: NodeId,
+    pub variant: &'static str,
+    pub problem: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "seed {} ({} nodes, {} edges, {} -> {}): {} {}",
+            self.seed, self.nodes, self.edges, self.source.0, self.target.0, self.variant, self.problem
+        )
+    }
+}
+
+/// Number of implementations each query is run through, including the
+/// Tier 1 baseline the others are compared to
+pub fn variant_count() -> usize {
+    1 + PATH_VARIANTS.len() + DISTANCE_VARIANTS.len()
+}
+
+/// Check every implementation against the Tier 1 baseline on a random
+/// query over each of `CROSS_CHECK_SIZES`, for seeds `0..seeds`. Returns the
+/// number of queries checked, or the first divergence.
+pub fn cross_check(seeds: u64) -> Result<usize, Divergence> {
+    let mut queries = 0;
+
+    for seed in 0..seeds {
+        for (nodes, edges) in CROSS_CHECK_SIZES {
+            let graph = Arc::new(Graph::random_connected_seeded(nodes, edges, 100, seed));
+            let mut rng = SimpleRng::new(seed);
+            let source = NodeId(rng.next_usize() % nodes);
+            let target = NodeId(rng.next_usize() % nodes);
+
+            let divergence = |variant, problem| Divergence {
+                seed,
+                nodes,
+                edges,
+                source,
+                target,
+                variant,
+                problem,
+            };
+
+            let expected = dijkstra_tier1_baseline(graph.clone(), source, target);
+            if let Err(err) = expected.verify(&graph) {
+                return Err(divergence("Tier 1 baseline", format!("returned an invalid path: {}", err)));
+            }
+
+            for (variant, run) in PATH_VARIANTS {
+                let result = run(graph.clone(), source, target);
+                if result.distance != expected.distance {
+                    return Err(divergence(
+                        variant,
+                        format!("found distance {:?}, expected {:?}", result.distance, expected.distance),
+                    ));
+                }
+                if let Err(err) = result.verify(&graph) {
+                    return Err(divergence(variant, format!("returned an invalid path: {}", err)));
+                }
+            }
+
+            for (variant, run) in DISTANCE_VARIANTS {
+                let distance = run(graph.clone(), source, target);
+                if distance != expected.distance {
+                    return Err(divergence(
+                        variant,
+                        format!("found distance {:?}, expected {:?}", distance, expected.distance),
+                    ));
+                }
+            }
+
+            queries += 1;
+        }
+    }
+
+    Ok(queries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_implementation_agrees_with_the_baseline() {
+        let queries = cross_check(5).unwrap_or_else(|divergence| panic!("{}", divergence));
+        assert_eq!(queries, 5 * CROSS_CHECK_SIZES.len());
+    }
+
+    #[test]
+    fn unreachable_targets_are_checked_without_panicking() {
+        let graph = Arc::new(Graph::new(3));
+        for (variant, run) in PATH_VARIANTS {
+            let result = run(graph.clone(), NodeId(0), NodeId(2));
+            assert_eq!(result.distance, None, "{}", variant);
+            assert!(result.path.is_empty(), "{}", variant);
+        }
+        for (variant, run) in DISTANCE_VARIANTS {
+            assert_eq!(run(graph.clone(), NodeId(0), NodeId(2)), None, "{}", variant);
+        }
+    }
+
+    #[test]
+    fn equal_cost_paths_resolve_to_the_forward_smallest() {
+        // 0 -> 1 -> 4 -> 5 and 0 -> 2 -> 3 -> 5 both cost 3. They part at
+        // the first hop, where 1 < 2 decides, though 3 < 4 before the target.
+        let mut graph = Graph::new(6);
+        for (from, to) in [(0, 1), (1, 4), (4, 5), (0, 2), (2, 3), (3, 5)] {
+            graph.add_edge(NodeId(from), NodeId(to), 1);
+        }
+        let graph = Arc::new(graph);
+        let expected = [NodeId(0), NodeId(1), NodeId(4), NodeId(5)];
+
+        assert_eq!(dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(5)).path, expected);
+        for (variant, run) in PATH_VARIANTS {
+            assert_eq!(run(graph.clone(), NodeId(0), NodeId(5)).path, expected, "{}", variant);
+        }
+    }
+}
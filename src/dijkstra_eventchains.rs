@@ -1,10 +1,15 @@
 use crate::dijkstra_events::*;
-use crate::eventchains::{EventChain, EventContext, FaultToleranceMode};
-use crate::graph::{Graph, NodeId, ShortestPathResult};
+use crate::eventchains::{
+    ChainResult, ChainableEvent, ConditionalEvent, DispatchStrategy, EventChain, EventContext,
+    EventMiddleware, EventResult, FaultToleranceMode, MiddlewarePriority, RepeatUntilEvent,
+};
+use crate::graph::{AllDistancesResult, Graph, NodeId, ShortestPathResult};
 use crate::middleware::{LoggingMiddleware, PerformanceMiddleware, TimingMiddleware};
+use crate::tier_baselines::dijkstra_tier1_baseline;
 
-use std::sync::Arc;
-use crate::noop_middleware::NoOpMiddleware;
+use colored::*;
+use std::sync::{Arc, Mutex};
+use crate::noop_middleware::{NoOpMiddleware, OrderRecordingMiddleware};
 
 /// Run Dijkstra using EventChains pattern (bare - no middleware)
 pub fn dijkstra_eventchains_bare(
@@ -12,64 +17,101 @@ pub fn dijkstra_eventchains_bare(
     source: NodeId,
     target: NodeId,
 ) -> ShortestPathResult {
-    let mut context = EventContext::new();
-    let node_count = graph.nodes;
-    context.set("graph", graph);
+    let chain = build_bare_chain(source, target, graph.nodes);
+    let mut context = EventContextBuilder::new().graph(graph).build();
+
+    // Execute chain
+    let result = chain.execute(&mut context);
+
+    if result.success {
+        context.get("result").unwrap()
+    } else {
+        ShortestPathResult::unreachable(source, target)
+    }
+}
 
+/// The four events of `dijkstra_eventchains_bare`, with no middleware
+fn build_bare_chain(source: NodeId, target: NodeId, node_count: usize) -> EventChain {
     let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
 
     chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
     chain.add_event(Box::new(InitializePriorityQueueEvent));
 
-    // Process nodes in a loop-like fashion
-    // This is a limitation of the pattern for inherently iterative algorithms
-    for _ in 0..node_count {
-        chain.add_event(Box::new(ProcessNodeEvent));
-    }
+    // Drain the queue one node at a time; ProcessNodeEvent records in
+    // "continue" whether the queue still has entries
+    chain.add_event(Box::new(RepeatUntilEvent::new(
+        Box::new(ProcessNodeEvent),
+        Box::new(|ctx: &EventContext| ctx.get::<bool>("continue").unwrap_or(true)),
+    )));
 
     chain.add_event(Box::new(FinalizeResultEvent::new(target)));
 
-    // Execute chain
+    chain
+}
+
+/// Run Dijkstra using EventChains pattern (bare), settling up to
+/// `batch_size` nodes per event execution. A batch size of 1 behaves like
+/// `dijkstra_eventchains_bare`; larger batches amortize the per-event
+/// context round-trip over more work.
+pub fn dijkstra_eventchains_batched(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    batch_size: usize,
+) -> ShortestPathResult {
+    let node_count = graph.nodes;
+    let mut context = EventContextBuilder::new().graph(graph).build();
+
+    let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
+
+    chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
+    chain.add_event(Box::new(InitializePriorityQueueEvent));
+    chain.add_event(Box::new(RepeatUntilEvent::new(
+        Box::new(ProcessBatchEvent::new(batch_size)),
+        Box::new(|ctx: &EventContext| ctx.get::<bool>("continue").unwrap_or(true)),
+    )));
+    chain.add_event(Box::new(FinalizeResultEvent::new(target)));
+
     let result = chain.execute(&mut context);
 
     if result.success {
         context.get("result").unwrap()
     } else {
-        ShortestPathResult {
-            source,
-            target,
-            distance: None,
-            path: Vec::new(),
-        }
+        ShortestPathResult::unreachable(source, target)
     }
 }
 
-/// Run Dijkstra using EventChains pattern with full middleware
+/// Run Dijkstra using EventChains pattern with full middleware.
+/// Also returns the per-event timings captured by `TimingMiddleware`.
 pub fn dijkstra_eventchains_full(
     graph: Arc<Graph>,
     source: NodeId,
     target: NodeId,
     verbose: bool,
-) -> ShortestPathResult {
-    let mut context = EventContext::new();
+) -> (ShortestPathResult, Vec<(String, u64)>) {
     let node_count = graph.nodes;
-    context.set("graph", graph);
+    let mut context = EventContextBuilder::new().graph(graph).build();
 
     let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
 
-    // Add middleware (reverse order of execution)
-    let perf_middleware = PerformanceMiddleware::new();
-    chain.use_middleware(Box::new(perf_middleware));
+    // Logging always runs outermost and performance tracking innermost,
+    // whatever order the middleware are registered in
+    chain.use_middleware_with_priority(Box::new(LoggingMiddleware::new(verbose)), MiddlewarePriority::Outermost);
+    chain.use_middleware_with_priority(Box::new(PerformanceMiddleware::new()), MiddlewarePriority::Innermost);
     chain.use_middleware(Box::new(TimingMiddleware::new(verbose)));
-    chain.use_middleware(Box::new(LoggingMiddleware::new(verbose)));
 
     // Add events
     chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
     chain.add_event(Box::new(InitializePriorityQueueEvent));
 
-    // Process nodes
+    // Process nodes. Once the queue is exhausted (e.g. the target is in
+    // another component) the remaining events are skipped rather than each
+    // cloning the state to find nothing to do
     for _ in 0..node_count {
-        chain.add_event(Box::new(ProcessNodeEvent));
+        chain.add_event(Box::new(ConditionalEvent::new(
+            Box::new(|ctx: &EventContext| ctx.get::<bool>("continue").unwrap_or(true)),
+            Box::new(ProcessNodeEvent),
+        )));
     }
 
     chain.add_event(Box::new(FinalizeResultEvent::new(target)));
@@ -77,16 +119,13 @@ pub fn dijkstra_eventchains_full(
     // Execute chain
     let result = chain.execute(&mut context);
 
-    if result.success {
+    let path = if result.success {
         context.get("result").unwrap()
     } else {
-        ShortestPathResult {
-            source,
-            target,
-            distance: None,
-            path: Vec::new(),
-        }
-    }
+        ShortestPathResult::unreachable(source, target)
+    };
+
+    (path, context.collected_timings())
 }
 
 /// Run Dijkstra using a more efficient EventChains approach
@@ -96,9 +135,8 @@ pub fn dijkstra_eventchains_optimized(
     source: NodeId,
     target: NodeId,
 ) -> ShortestPathResult {
-    let mut context = EventContext::new();
     let node_count = graph.nodes;
-    context.set("graph", graph);
+    let mut context = EventContextBuilder::new().graph(graph).build();
 
     let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
 
@@ -116,15 +154,225 @@ pub fn dijkstra_eventchains_optimized(
     if result.success {
         context.get("result").unwrap()
     } else {
-        ShortestPathResult {
+        ShortestPathResult::unreachable(source, target)
+    }
+}
+
+/// The optimized chain's events as a closed set, so running one is a
+/// `match` the compiler can inline rather than a vtable call
+enum OptimizedEvent {
+    InitializeState(InitializeStateEvent),
+    InitializePriorityQueue(InitializePriorityQueueEvent),
+    ProcessAllNodes(ProcessAllNodesEvent),
+    FinalizeResult(FinalizeResultEvent),
+}
+
+impl OptimizedEvent {
+    fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+        match self {
+            OptimizedEvent::InitializeState(event) => event.execute(context),
+            OptimizedEvent::InitializePriorityQueue(event) => event.execute(context),
+            OptimizedEvent::ProcessAllNodes(event) => event.execute(context),
+            OptimizedEvent::FinalizeResult(event) => event.execute(context),
+        }
+    }
+}
+
+/// `dijkstra_eventchains_optimized`'s events run with static dispatch: an
+/// unboxed array of `OptimizedEvent`, executed in order until one fails.
+/// Compare against `dijkstra_eventchains_dyn_loop`, which differs only in
+/// boxing the events and calling them through `dyn ChainableEvent`.
+pub fn dijkstra_eventchains_static(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let node_count = graph.nodes;
+    let mut context = EventContextBuilder::new().graph(graph).build();
+
+    let events = [
+        OptimizedEvent::InitializeState(InitializeStateEvent::new(source, node_count)),
+        OptimizedEvent::InitializePriorityQueue(InitializePriorityQueueEvent),
+        OptimizedEvent::ProcessAllNodes(ProcessAllNodesEvent),
+        OptimizedEvent::FinalizeResult(FinalizeResultEvent::new(target)),
+    ];
+
+    for event in &events {
+        if !event.execute(&mut context).is_success() {
+            return ShortestPathResult::unreachable(source, target);
+        }
+    }
+
+    context.get("result").unwrap()
+}
+
+/// The dynamic-dispatch twin of `dijkstra_eventchains_static`: the same
+/// loop over the same events, boxed as `dyn ChainableEvent`. Neither uses
+/// `EventChain`, so the two differ only in dispatch.
+pub fn dijkstra_eventchains_dyn_loop(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let node_count = graph.nodes;
+    let mut context = EventContextBuilder::new().graph(graph).build();
+
+    let events: [Box<dyn ChainableEvent>; 4] = [
+        Box::new(InitializeStateEvent::new(source, node_count)),
+        Box::new(InitializePriorityQueueEvent),
+        Box::new(ProcessAllNodesEvent),
+        Box::new(FinalizeResultEvent::new(target)),
+    ];
+
+    for event in &events {
+        if !event.execute(&mut context).is_success() {
+            return ShortestPathResult::unreachable(source, target);
+        }
+    }
+
+    context.get("result").unwrap()
+}
+
+/// Run the optimized EventChains Dijkstra, returning only the distance to
+/// `target`; `None` if unreachable or if the chain fails
+pub fn dijkstra_eventchains_distance_only(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> Option<u32> {
+    let node_count = graph.nodes;
+    let mut context = EventContextBuilder::new().graph(graph).build();
+
+    let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
+
+    chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
+    chain.add_event(Box::new(InitializePriorityQueueEvent));
+    chain.add_event(Box::new(ProcessAllNodesEvent));
+    chain.add_event(Box::new(FinalizeDistanceEvent::new(target)));
+
+    let result = chain.execute(&mut context);
+
+    if result.success {
+        context.get("distance").unwrap()
+    } else {
+        None
+    }
+}
+
+/// Run the optimized EventChains Dijkstra without a target, returning the
+/// distance from `source` to every node
+pub fn dijkstra_eventchains_all_distances(graph: Arc<Graph>, source: NodeId) -> AllDistancesResult {
+    let node_count = graph.nodes;
+    let mut context = EventContextBuilder::new().graph(graph).build();
+
+    let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
+
+    chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
+    chain.add_event(Box::new(InitializePriorityQueueEvent));
+    chain.add_event(Box::new(ProcessAllNodesEvent));
+    chain.add_event(Box::new(FinalizeAllDistancesEvent));
+
+    let result = chain.execute(&mut context);
+
+    if result.success {
+        context.get("all_distances").unwrap()
+    } else {
+        AllDistancesResult {
             source,
-            target,
-            distance: None,
-            path: Vec::new(),
+            distances: vec![None; node_count],
+            predecessors: vec![None; node_count],
+        }
+    }
+}
+
+/// Run the optimized EventChains Dijkstra, falling back to the bare baseline
+/// if the chain fails. Unlike `dijkstra_eventchains_optimized`, a framework
+/// failure is never reported as "unreachable": the result stays correct and
+/// a warning naming the failed events is printed to stderr.
+pub fn dijkstra_eventchains_optimized_checked(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    dijkstra_eventchains_optimized_checked_with(graph, source, target, &mut print_warning)
+}
+
+/// Like `dijkstra_eventchains_optimized_checked`, passing the fallback
+/// warning to `warn` instead of printing it
+pub fn dijkstra_eventchains_optimized_checked_with(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    warn: &mut dyn FnMut(&str),
+) -> ShortestPathResult {
+    let node_count = graph.nodes;
+    let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
+
+    chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
+    chain.add_event(Box::new(InitializePriorityQueueEvent));
+    chain.add_event(Box::new(ProcessAllNodesEvent));
+    chain.add_event(Box::new(FinalizeResultEvent::new(target)));
+
+    execute_or_fall_back(&chain, graph, source, target, warn)
+}
+
+/// Execute `chain` over a context holding `graph` and return its "result",
+/// or the baseline's answer if the chain fails or produces none. A chain
+/// whose events are missing a dependency is not run at all.
+fn execute_or_fall_back(
+    chain: &EventChain,
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    warn: &mut dyn FnMut(&str),
+) -> ShortestPathResult {
+    let mut context = EventContextBuilder::new().graph(graph.clone()).build();
+    if let Err(missing) = chain.validate_for(&context) {
+        warn(&invalid_chain_warning(&missing));
+        return dijkstra_tier1_baseline(graph, source, target);
+    }
+
+    let result = chain.execute(&mut context);
+
+    match context.get("result") {
+        Some(path) if result.success => path,
+        _ => {
+            // Recompute with the bare baseline, but don't silently mask the
+            // framework failure
+            warn(&fallback_warning(&result));
+            dijkstra_tier1_baseline(graph, source, target)
         }
     }
 }
 
+/// Default fallback warning sink
+fn print_warning(warning: &str) {
+    eprintln!("{} {}", "Warning:".yellow().bold(), warning);
+}
+
+/// Why a chain that failed validation was not run
+fn invalid_chain_warning(missing: &[String]) -> String {
+    format!("EventChains chain is invalid [{}]; falling back to baseline", missing.join(", "))
+}
+
+/// Why `fallback_to_baseline` was needed, naming each failed event
+fn fallback_warning(chain_result: &ChainResult) -> String {
+    let failed: Vec<String> = chain_result
+        .failures
+        .iter()
+        .map(|f| format!("{} ({})", f.event_name, f.error_message))
+        .collect();
+
+    format!(
+        "EventChains path failed [{}]; falling back to baseline",
+        if failed.is_empty() {
+            "no result produced".to_string()
+        } else {
+            failed.join(", ")
+        }
+    )
+}
+
 /// Run Dijkstra using optimized EventChains with logging and timing middleware
 /// This is for fair comparison in Tier 4 benchmarks
 pub fn dijkstra_eventchains_optimized_with_middleware(
@@ -133,9 +381,8 @@ pub fn dijkstra_eventchains_optimized_with_middleware(
     target: NodeId,
     verbose: bool,
 ) -> ShortestPathResult {
-    let mut context = EventContext::new();
     let node_count = graph.nodes;
-    context.set("graph", graph);
+    let mut context = EventContextBuilder::new().graph(graph).build();
 
     let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
 
@@ -155,12 +402,63 @@ pub fn dijkstra_eventchains_optimized_with_middleware(
     if result.success {
         context.get("result").unwrap()
     } else {
-        ShortestPathResult {
-            source,
-            target,
-            distance: None,
-            path: Vec::new(),
-        }
+        ShortestPathResult::unreachable(source, target)
+    }
+}
+
+/// Run the optimized EventChains Dijkstra under `mode`, returning the
+/// chain's outcome alongside the result. With `BestEffort` a failed event's
+/// defaults let the rest of the chain run, so a result is produced even for
+/// an invalid query; otherwise an unreachable result stands in for the
+/// missing one.
+pub fn dijkstra_eventchains_with_fault_tolerance(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    mode: FaultToleranceMode,
+) -> (ShortestPathResult, ChainResult) {
+    let node_count = graph.nodes;
+    let mut context = EventContextBuilder::new().graph(graph).build();
+
+    let mut chain = EventChain::new().with_fault_tolerance(mode);
+    chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
+    chain.add_event(Box::new(InitializePriorityQueueEvent));
+    chain.add_event(Box::new(ProcessAllNodesEvent));
+    chain.add_event(Box::new(FinalizeResultEvent::new(target)));
+
+    let chain_result = chain.execute(&mut context);
+    let result = context
+        .take("result")
+        .unwrap_or_else(|| ShortestPathResult::unreachable(source, target));
+    (result, chain_result)
+}
+
+/// Run the optimized EventChains Dijkstra with `middleware` as its only
+/// middleware, to measure the cost of one middleware in isolation. The
+/// context carries "target" so `MemoizingMiddleware` can key on the query.
+pub fn dijkstra_eventchains_with_middleware(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    middleware: Box<dyn EventMiddleware>,
+) -> ShortestPathResult {
+    let node_count = graph.nodes;
+    let mut context = EventContextBuilder::new().graph(graph).target(target).build();
+
+    let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
+    chain.use_middleware(middleware);
+
+    chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
+    chain.add_event(Box::new(InitializePriorityQueueEvent));
+    chain.add_event(Box::new(ProcessAllNodesEvent));
+    chain.add_event(Box::new(FinalizeResultEvent::new(target)));
+
+    let result = chain.execute(&mut context);
+
+    if result.success {
+        context.get("result").unwrap()
+    } else {
+        ShortestPathResult::unreachable(source, target)
     }
 }
 
@@ -170,12 +468,55 @@ pub fn dijkstra_eventchains_with_n_middleware(
     target: NodeId,
     n: usize,
 ) -> ShortestPathResult {
-    let mut context = EventContext::new();
+    dijkstra_eventchains_with_dispatch(graph, source, target, n, DispatchStrategy::Recursive)
+}
+
+/// Run the optimized EventChains Dijkstra with one `OrderRecordingMiddleware`
+/// per label, registered in order, and return the shared log of every
+/// "{label}:before" and "{label}:after" entry across all four events
+pub fn middleware_call_order(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    labels: &[&str],
+    dispatch: DispatchStrategy,
+) -> Vec<String> {
     let node_count = graph.nodes;
-    context.set("graph", graph);
+    let mut context = EventContextBuilder::new().graph(graph).build();
+    let log = Arc::new(Mutex::new(Vec::new()));
 
     let mut chain = EventChain::new()
-        .with_fault_tolerance(FaultToleranceMode::Strict);
+        .with_fault_tolerance(FaultToleranceMode::Strict)
+        .with_dispatch(dispatch);
+    for label in labels {
+        chain.use_middleware(Box::new(OrderRecordingMiddleware::new(label, log.clone())));
+    }
+
+    chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
+    chain.add_event(Box::new(InitializePriorityQueueEvent));
+    chain.add_event(Box::new(ProcessAllNodesEvent));
+    chain.add_event(Box::new(FinalizeResultEvent::new(target)));
+
+    chain.execute(&mut context);
+    let order = log.lock().unwrap().clone();
+    order
+}
+
+/// Like `dijkstra_eventchains_with_n_middleware`, dispatching through the
+/// middleware with `dispatch`
+pub fn dijkstra_eventchains_with_dispatch(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    n: usize,
+    dispatch: DispatchStrategy,
+) -> ShortestPathResult {
+    let node_count = graph.nodes;
+    let mut context = EventContextBuilder::new().graph(graph).build();
+
+    let mut chain = EventChain::new()
+        .with_fault_tolerance(FaultToleranceMode::Strict)
+        .with_dispatch(dispatch);
 
     // Add n no-op middleware layers
     for i in 0..n {
@@ -194,15 +535,35 @@ pub fn dijkstra_eventchains_with_n_middleware(
     if result.success {
         context.get("result").unwrap()
     } else {
-        ShortestPathResult {
-            source,
-            target,
-            distance: None,
-            path: Vec::new(),
-        }
+        ShortestPathResult::unreachable(source, target)
     }
 }
 
+/// Build (without executing) the optimized chain with the full middleware
+/// stack: four events plus performance, timing, and logging middleware.
+/// Used to measure chain construction cost in isolation.
+pub fn build_optimized_chain(
+    source: NodeId,
+    target: NodeId,
+    node_count: usize,
+    verbose: bool,
+) -> EventChain {
+    let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Strict);
+
+    // Each is inserted innermost, leaving performance closest to the events,
+    // then timing, then logging outermost
+    chain.use_middleware_with_priority(Box::new(LoggingMiddleware::new(verbose)), MiddlewarePriority::At(0));
+    chain.use_middleware_with_priority(Box::new(TimingMiddleware::new(verbose)), MiddlewarePriority::At(0));
+    chain.use_middleware_with_priority(Box::new(PerformanceMiddleware::new()), MiddlewarePriority::At(0));
+
+    chain.add_event(Box::new(InitializeStateEvent::new(source, node_count)));
+    chain.add_event(Box::new(InitializePriorityQueueEvent));
+    chain.add_event(Box::new(ProcessAllNodesEvent));
+    chain.add_event(Box::new(FinalizeResultEvent::new(target)));
+
+    chain
+}
+
 /// Event that processes all nodes in one go (more efficient)
 struct ProcessAllNodesEvent;
 
@@ -212,30 +573,35 @@ impl crate::eventchains::ChainableEvent for ProcessAllNodesEvent {
         use crate::graph::{DijkstraState, Graph, QueueNode};
         use std::collections::BinaryHeap;
 
-        // Get references from context - note: these will be cloned
-        let queue: BinaryHeap<QueueNode> = match context.get("queue") {
-            Some(q) => q,
-            None => return EventResult::Failure("Queue not found".to_string()),
+        let graph: Arc<Graph> = match context.get("graph") {
+            Some(g) => g,
+            None => return EventResult::Failure("Graph not found in context".to_string()),
         };
 
-        let mut state: DijkstraState = match context.get("state") {
-            Some(s) => s,
-            None => return EventResult::Failure("State not found".to_string()),
+        // Take the queue (it is fully drained here) and update the state in
+        // place, so neither is cloned
+        let mut queue: BinaryHeap<QueueNode> = match context.take("queue") {
+            Some(q) => q,
+            None => return EventResult::Failure("Queue not found in context".to_string()),
         };
 
-        let graph: Arc<Graph> = match context.get("graph") {
-            Some(g) => g,
-            None => return EventResult::Failure("Graph not found".to_string()),
+        let state: &mut DijkstraState = match context.get_mut("state") {
+            Some(s) => s,
+            None => {
+                context.set("queue", queue);
+                return EventResult::Failure("State not found in context".to_string());
+            }
         };
 
-        let mut queue = queue;
-
         while let Some(QueueNode { node, distance }) = queue.pop() {
-            if state.visited[node.0] || distance > state.distances[node.0] {
+            if state.visited.get(node.0).copied().unwrap_or(true)
+                || distance > state.distances[node.0]
+            {
                 continue;
             }
 
             state.visited[node.0] = true;
+            state.nodes_expanded += 1;
 
             for edge in &graph.adjacency_list[node.0] {
                 let new_distance = distance.saturating_add(edge.weight);
@@ -248,15 +614,395 @@ impl crate::eventchains::ChainableEvent for ProcessAllNodesEvent {
                         node: edge.to,
                         distance: new_distance,
                     });
+                } else if new_distance == state.distances[edge.to.0] {
+                    state.break_tie(node, edge.to);
                 }
             }
         }
 
-        context.set("state", state);
         EventResult::Success(())
     }
 
     fn name(&self) -> &str {
         "ProcessAllNodes"
     }
+
+    // The drained queue is consumed rather than put back
+    fn required_keys(&self) -> &[&str] {
+        &["graph", "queue", "state"]
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        &["state"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::{MemoizingMiddleware, PathCache};
+    use crate::eventchains::ChainStatus;
+    use crate::graph::DijkstraState;
+
+    #[test]
+    fn bare_chain_drains_the_queue_in_four_events() {
+        let graph = Arc::new(Graph::random_connected_seeded(60, 200, 100, 5));
+        let (source, target) = (NodeId(2), NodeId(57));
+
+        let chain = build_bare_chain(source, target, graph.nodes);
+        let mut context = EventContextBuilder::new().graph(graph.clone()).build();
+        let (result, timings) = chain.execute_timed(&mut context);
+        assert!(result.success);
+        assert_eq!(timings.len(), 4);
+
+        let bare = dijkstra_eventchains_bare(graph.clone(), source, target);
+        let optimized = dijkstra_eventchains_optimized(graph, source, target);
+        assert_eq!(bare.distance, optimized.distance);
+        assert_eq!(bare.path, optimized.path);
+    }
+
+    #[test]
+    fn full_chain_with_owned_events_and_middleware_runs() {
+        let graph = Arc::new(Graph::random_connected_seeded(30, 90, 100, 3));
+        let expected = dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(29));
+
+        let (result, timings) = dijkstra_eventchains_full(graph, NodeId(0), NodeId(29), false);
+
+        assert_eq!(result.distance, expected.distance);
+        assert!(timings.iter().any(|(name, _)| name == "FinalizeResult"));
+    }
+
+    #[test]
+    fn timing_middleware_records_process_all_nodes() {
+        let graph = Arc::new(Graph::random_connected_seeded(200, 800, 100, 4));
+
+        let mut chain = EventChain::new();
+        chain.use_middleware(Box::new(TimingMiddleware::new(false)));
+        chain.add_event(Box::new(InitializeStateEvent::new(NodeId(0), graph.nodes)));
+        chain.add_event(Box::new(InitializePriorityQueueEvent));
+        chain.add_event(Box::new(ProcessAllNodesEvent));
+        chain.add_event(Box::new(FinalizeResultEvent::new(NodeId(199))));
+        let mut context = EventContextBuilder::new().graph(graph).build();
+        assert!(chain.execute(&mut context).success);
+
+        let timings = context.collected_timings();
+        let nanos = timings
+            .iter()
+            .find(|(name, _)| name == "ProcessAllNodes")
+            .map(|&(_, nanos)| nanos);
+        assert!(matches!(nanos, Some(n) if n > 0), "{:?}", timings);
+    }
+
+    #[test]
+    fn broken_chain_falls_back_to_the_baseline_distance() {
+        let graph = Arc::new(Graph::random_connected_seeded(40, 120, 100, 7));
+        let (source, target) = (NodeId(0), NodeId(39));
+
+        // No InitializePriorityQueueEvent, so ProcessAllNodes finds no queue
+        let mut chain = EventChain::new();
+        chain.add_event(Box::new(InitializeStateEvent::new(source, graph.nodes)));
+        chain.add_event(Box::new(ProcessAllNodesEvent));
+        chain.add_event(Box::new(FinalizeResultEvent::new(target)));
+
+        let mut context = EventContextBuilder::new().graph(graph.clone()).build();
+        let missing = chain.validate_for(&context).unwrap_err();
+        assert_eq!(
+            invalid_chain_warning(&missing),
+            "EventChains chain is invalid [ProcessAllNodes requires 'queue', which no earlier event provides]; \
+             falling back to baseline"
+        );
+        let chain_result = chain.execute(&mut context);
+        assert!(!chain_result.success);
+        assert_eq!(
+            fallback_warning(&chain_result),
+            "EventChains path failed [ProcessAllNodes (Queue not found in context)]; falling back to baseline"
+        );
+
+        let expected = dijkstra_tier1_baseline(graph.clone(), source, target);
+        let mut warnings = Vec::new();
+        let result = execute_or_fall_back(&chain, graph, source, target, &mut |w| warnings.push(w.to_string()));
+        assert!(expected.distance.is_some());
+        assert_eq!(result.distance, expected.distance);
+        assert_eq!(result.path, expected.path);
+        assert_eq!(warnings, vec![invalid_chain_warning(&missing)]);
+    }
+
+    #[test]
+    fn checked_chain_matches_the_baseline_without_falling_back() {
+        let graph = Arc::new(Graph::random_connected_seeded(40, 120, 100, 8));
+        let expected = dijkstra_tier1_baseline(graph.clone(), NodeId(3), NodeId(30));
+        let mut warnings = 0;
+        let result = dijkstra_eventchains_optimized_checked_with(graph, NodeId(3), NodeId(30), &mut |_| warnings += 1);
+        assert_eq!(result.distance, expected.distance);
+        assert_eq!(result.path, expected.path);
+        assert_eq!(warnings, 0);
+    }
+
+    #[test]
+    fn memoized_chain_serves_repeat_queries_from_the_shared_cache() {
+        let graph = Arc::new(Graph::random_connected_seeded(40, 120, 100, 9));
+        let expected = dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(39));
+        let cache = PathCache::default();
+
+        for _ in 0..3 {
+            let memoizing = Box::new(MemoizingMiddleware::with_cache(cache.clone()));
+            let result = dijkstra_eventchains_with_middleware(graph.clone(), NodeId(0), NodeId(39), memoizing);
+            assert_eq!(result.distance, expected.distance);
+            assert_eq!(result.path, expected.path);
+        }
+        assert_eq!(cache.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn invalidated_cache_serves_the_updated_graph() {
+        let mut graph = Arc::new(Graph::random_connected_seeded(40, 120, 100, 9));
+        let memoizing = MemoizingMiddleware::new();
+        let query = |graph: &Arc<Graph>| {
+            let shared = Box::new(MemoizingMiddleware::with_cache(memoizing.cache()));
+            dijkstra_eventchains_with_middleware(graph.clone(), NodeId(0), NodeId(39), shared)
+        };
+        let before = query(&graph);
+        let (from, to) = before.segments().next().unwrap();
+
+        let old_weight = Arc::make_mut(&mut graph).update_edge_weight(from, to, 1_000).unwrap();
+        // Without invalidation the stale path is served
+        assert_eq!(query(&graph).distance, before.distance);
+
+        memoizing.invalidate_edge(from, to, old_weight, 1_000);
+        let after = query(&graph);
+        let expected = dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(39));
+        assert_ne!(after.distance, before.distance);
+        assert_eq!(after.distance, expected.distance);
+        assert_eq!(after.path, expected.path);
+    }
+
+    fn run_with_invalid_source(mode: FaultToleranceMode) -> (ShortestPathResult, ChainResult) {
+        let graph = Arc::new(Graph::random_connected_seeded(10, 20, 100, 1));
+        // InitializeState rejects a source outside the graph
+        dijkstra_eventchains_with_fault_tolerance(graph, NodeId(10), NodeId(9), mode)
+    }
+
+    #[test]
+    fn strict_chain_stops_at_the_failed_event() {
+        let (result, chain) = run_with_invalid_source(FaultToleranceMode::Strict);
+        assert_eq!(chain.status, ChainStatus::Failed);
+        assert_eq!(chain.failures.len(), 1);
+        assert_eq!(chain.failures[0].event_name, "InitializeState");
+        assert_eq!(result.distance, None);
+    }
+
+    #[test]
+    fn lenient_chain_runs_on_with_warnings() {
+        let (result, chain) = run_with_invalid_source(FaultToleranceMode::Lenient);
+        assert_eq!(chain.status, ChainStatus::CompletedWithWarnings);
+        // Every later event misses what InitializeState would have set
+        let failed: Vec<&str> = chain.failures.iter().map(|f| f.event_name.as_str()).collect();
+        assert_eq!(
+            failed,
+            ["InitializeState", "InitializePriorityQueue", "ProcessAllNodes", "FinalizeResult"]
+        );
+        assert_eq!(result.distance, None);
+    }
+
+    #[test]
+    fn best_effort_chain_finalizes_an_empty_result() {
+        let (result, chain) = run_with_invalid_source(FaultToleranceMode::BestEffort);
+        assert_eq!(chain.status, ChainStatus::CompletedWithWarnings);
+        assert_eq!(chain.failures.len(), 1);
+        assert_eq!(result.target, NodeId(9));
+        assert_eq!(result.distance, None);
+        assert!(result.path.is_empty());
+    }
+
+    type PathVariant = fn(Arc<Graph>, NodeId, NodeId) -> ShortestPathResult;
+
+    #[test]
+    fn target_in_another_component_stops_after_the_source_component() {
+        // 0 -> 1 -> 2 and 0 -> 2, with 3 <-> 4 cut off from the source
+        let mut graph = Graph::new(5);
+        graph.add_edge(NodeId(0), NodeId(1), 2);
+        graph.add_edge(NodeId(1), NodeId(2), 3);
+        graph.add_edge(NodeId(0), NodeId(2), 7);
+        graph.add_bidirectional_edge(NodeId(3), NodeId(4), 1);
+        let graph = Arc::new(graph);
+
+        let variants: [(&str, PathVariant); 4] = [
+            ("bare", dijkstra_eventchains_bare),
+            ("optimized", dijkstra_eventchains_optimized),
+            ("batched", |g, s, t| dijkstra_eventchains_batched(g, s, t, 2)),
+            ("full", |g, s, t| dijkstra_eventchains_full(g, s, t, false).0),
+        ];
+        for (variant, run) in variants {
+            let result = run(graph.clone(), NodeId(0), NodeId(4));
+            assert_eq!(result.distance, None, "{}", variant);
+            assert!(result.path.is_empty(), "{}", variant);
+            // The queue ran dry once 0, 1 and 2 were settled
+            assert_eq!(result.nodes_expanded, 3, "{}", variant);
+        }
+    }
+
+    #[test]
+    fn all_distances_leaves_an_isolated_component_unreachable() {
+        // 0 -> 1 -> 2 and 0 -> 2, with 3 <-> 4 cut off from the source
+        let mut graph = Graph::new(5);
+        graph.add_edge(NodeId(0), NodeId(1), 2);
+        graph.add_edge(NodeId(1), NodeId(2), 3);
+        graph.add_edge(NodeId(0), NodeId(2), 7);
+        graph.add_edge(NodeId(3), NodeId(4), 1);
+        graph.add_edge(NodeId(4), NodeId(3), 1);
+        let graph = Arc::new(graph);
+
+        let all = dijkstra_eventchains_all_distances(graph.clone(), NodeId(0));
+        assert_eq!(all.distances, [Some(0), Some(2), Some(5), None, None]);
+        assert_eq!(all.reachable_count(), 3);
+
+        for node in 0..graph.nodes {
+            let traditional = dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(node));
+            assert_eq!(all.distances[node], traditional.distance, "node {}", node);
+        }
+        assert_eq!(all.predecessors[2], Some(NodeId(1)));
+        assert_eq!(all.predecessors[3], None);
+    }
+
+    #[test]
+    fn early_termination_expands_fewer_nodes_than_a_full_drain() {
+        let graph = Arc::new(Graph::random_connected_seeded(200, 800, 100, 3));
+        // A neighbor of the source is settled long before the queue drains
+        let source = NodeId(0);
+        let target = graph.adjacency_list[0][0].to;
+
+        let early = dijkstra_tier1_baseline(graph.clone(), source, target);
+        // ProcessAllNodes has no target to stop at
+        let drained = dijkstra_eventchains_optimized(graph.clone(), source, target);
+
+        assert_eq!(early.distance, drained.distance);
+        assert_eq!(drained.nodes_expanded, graph.nodes);
+        assert!(early.nodes_expanded < drained.nodes_expanded, "{}", early.nodes_expanded);
+    }
+
+    #[test]
+    fn dispatch_strategies_nest_middleware_identically() {
+        let graph = Arc::new(Graph::random_connected_seeded(20, 60, 100, 4));
+        let order = |dispatch| middleware_call_order(graph.clone(), NodeId(0), NodeId(19), &["A", "B"], dispatch);
+
+        let recursive = order(DispatchStrategy::Recursive);
+        assert_eq!(recursive.len(), 4 * 4);
+        assert_eq!(recursive[..4], ["B:before", "A:before", "A:after", "B:after"]);
+        assert_eq!(recursive, order(DispatchStrategy::Iterative));
+    }
+
+    #[test]
+    fn dispatch_strategies_leave_identical_results_and_context() {
+        let graph = Arc::new(Graph::random_connected_seeded(50, 200, 100, 6));
+        let run = |dispatch| {
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let mut chain = EventChain::new()
+                .with_fault_tolerance(FaultToleranceMode::Strict)
+                .with_dispatch(dispatch);
+            for label in ["A", "B", "C", "D", "E"] {
+                chain.use_middleware(Box::new(OrderRecordingMiddleware::new(label, log.clone())));
+            }
+            chain.add_event(Box::new(InitializeStateEvent::new(NodeId(0), graph.nodes)));
+            chain.add_event(Box::new(InitializePriorityQueueEvent));
+            chain.add_event(Box::new(ProcessAllNodesEvent));
+            chain.add_event(Box::new(FinalizeResultEvent::new(NodeId(49))));
+
+            let mut context = EventContextBuilder::new().graph(graph.clone()).build();
+            let result = chain.execute(&mut context);
+            let state = context.get::<DijkstraState>("state").unwrap();
+            let path = context.get::<ShortestPathResult>("result").unwrap();
+            let order = log.lock().unwrap().clone();
+            (
+                (result.success, result.status, result.failures.len()),
+                (state.distances, state.predecessors, state.visited),
+                (path.distance, path.path),
+                order,
+            )
+        };
+
+        let recursive = run(DispatchStrategy::Recursive);
+        assert_eq!(recursive.0, (true, ChainStatus::Completed, 0));
+        assert_eq!(recursive.3.len(), 4 * 5 * 2);
+        assert_eq!(recursive, run(DispatchStrategy::Iterative));
+    }
+
+    #[test]
+    fn replay_of_the_optimized_chain_lists_its_four_events_in_order() {
+        use crate::middleware::ReplayMiddleware;
+
+        let graph = Arc::new(Graph::random_connected_seeded(50, 200, 100, 8));
+        let mut chain = build_optimized_chain(NodeId(0), NodeId(49), graph.nodes, false);
+        let replay_middleware = ReplayMiddleware::new();
+        let replay = replay_middleware.replay();
+        chain.use_middleware(Box::new(replay_middleware));
+
+        let mut context = EventContextBuilder::new().graph(graph).build();
+        assert!(chain.execute(&mut context).success);
+
+        let entries = replay.entries();
+        let names: Vec<&str> = entries.iter().map(|entry| entry.event_name.as_str()).collect();
+        assert_eq!(names, ["InitializeState", "InitializePriorityQueue", "ProcessAllNodes", "FinalizeResult"]);
+        assert!(entries.iter().all(|entry| entry.success));
+        assert!(entries.windows(2).all(|pair| pair[0].timestamp_ns <= pair[1].timestamp_ns));
+
+        let dumped: Vec<serde_json::Value> = serde_json::from_str(&replay.dump()).unwrap();
+        assert_eq!(dumped.len(), 4);
+        assert_eq!(dumped[2]["event_name"], "ProcessAllNodes");
+    }
+
+    #[test]
+    fn reused_context_matches_fresh_ones_with_fewer_allocations() {
+        use crate::comprehensive_benchmarking::{bench_lock, run_comprehensive_benchmark};
+        let _lock = bench_lock();
+
+        let graph = Arc::new(Graph::random_connected_seeded(100, 400, 100, 10));
+        let chain = build_bare_chain(NodeId(0), NodeId(99), graph.nodes);
+        let query = |context: &mut EventContext| {
+            assert!(chain.execute(context).success);
+            context.get::<ShortestPathResult>("result").unwrap()
+        };
+
+        let mut reused = EventContext::new();
+        for _ in 0..3 {
+            reused.clear();
+            reused.set("graph", graph.clone());
+            let fresh = query(&mut EventContextBuilder::new().graph(graph.clone()).build());
+            let again = query(&mut reused);
+            assert_eq!((again.distance, again.path), (fresh.distance, fresh.path));
+        }
+
+        let fresh = run_comprehensive_benchmark(10, || {
+            query(&mut EventContextBuilder::new().graph(graph.clone()).build());
+            Ok(())
+        });
+        let cleared = run_comprehensive_benchmark(10, || {
+            reused.clear();
+            reused.set("graph", graph.clone());
+            query(&mut reused);
+            Ok(())
+        });
+        assert!(
+            cleared.allocations_per_run < fresh.allocations_per_run,
+            "{} vs {}",
+            cleared.allocations_per_run,
+            fresh.allocations_per_run
+        );
+    }
+
+    #[test]
+    fn static_dispatch_matches_the_dyn_chain() {
+        let graph = Arc::new(Graph::random_connected_seeded(300, 1_500, 100, 11));
+        for target in [0, 7, 150, 299] {
+            let (source, target) = (NodeId(3), NodeId(target));
+            let fixed = dijkstra_eventchains_static(graph.clone(), source, target);
+            for dynamic in [
+                dijkstra_eventchains_dyn_loop(graph.clone(), source, target),
+                dijkstra_eventchains_optimized(graph.clone(), source, target),
+            ] {
+                assert_eq!(fixed.distance, dynamic.distance, "target {:?}", target);
+                assert_eq!(fixed.path, dynamic.path, "target {:?}", target);
+                assert_eq!(fixed.nodes_expanded, dynamic.nodes_expanded, "target {:?}", target);
+            }
+        }
+    }
 }
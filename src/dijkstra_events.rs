@@ -1,8 +1,59 @@
 use crate::eventchains::{ChainableEvent, EventContext, EventResult};
-use crate::graph::{DijkstraState, Graph, NodeId, QueueNode};
+use crate::graph::{AllDistancesResult, DijkstraState, Graph, NodeId, QueueNode};
 use std::collections::BinaryHeap;
 use std::sync::Arc;
 
+/// Builds an `EventContext` with the entries the Dijkstra events read, so
+/// entry points don't hand-write context keys
+#[derive(Default)]
+pub struct EventContextBuilder {
+    graph: Option<Arc<Graph>>,
+    source: Option<NodeId>,
+    target: Option<NodeId>,
+}
+
+impl EventContextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Graph read by the node-processing events
+    pub fn graph(mut self, graph: Arc<Graph>) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
+    /// Source node, for chains that don't start with `InitializeStateEvent`
+    /// (which sets it itself)
+    #[allow(dead_code, reason = "every driver chain starts with `InitializeStateEvent`, which sets the source")]
+    pub fn source(mut self, source: NodeId) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Target node, the other half of `MemoizingMiddleware`'s cache key
+    pub fn target(mut self, target: NodeId) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Context with every entry given so far. Entries left unset are simply
+    /// absent; the event that needs one fails with "... not found in context".
+    pub fn build(self) -> EventContext {
+        let mut context = EventContext::new();
+        if let Some(graph) = self.graph {
+            context.set("graph", graph);
+        }
+        if let Some(source) = self.source {
+            context.set("source", source);
+        }
+        if let Some(target) = self.target {
+            context.set("target", target);
+        }
+        context
+    }
+}
+
 /// Event: Initialize Dijkstra's algorithm state
 pub struct InitializeStateEvent {
     source: NodeId,
@@ -17,6 +68,13 @@ impl InitializeStateEvent {
 
 impl ChainableEvent for InitializeStateEvent {
     fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+        if self.source.0 >= self.node_count {
+            return EventResult::Failure(format!(
+                "Source node {} out of range for {} nodes",
+                self.source.0, self.node_count
+            ));
+        }
+
         let state = DijkstraState::new(self.node_count, self.source);
         context.set("state", state);
         context.set("source", self.source);
@@ -26,6 +84,15 @@ impl ChainableEvent for InitializeStateEvent {
     fn name(&self) -> &str {
         "InitializeState"
     }
+
+    fn provided_keys(&self) -> &[&str] {
+        &["state", "source"]
+    }
+
+    fn populate_defaults(&self, context: &mut EventContext) {
+        context.set("state", DijkstraState::unreachable(self.node_count));
+        context.set("source", self.source);
+    }
 }
 
 /// Event: Create and initialize priority queue
@@ -51,6 +118,18 @@ impl ChainableEvent for InitializePriorityQueueEvent {
     fn name(&self) -> &str {
         "InitializePriorityQueue"
     }
+
+    fn required_keys(&self) -> &[&str] {
+        &["source"]
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        &["queue"]
+    }
+
+    fn populate_defaults(&self, context: &mut EventContext) {
+        context.set("queue", BinaryHeap::<QueueNode>::new());
+    }
 }
 
 /// Event: Process one node from the priority queue
@@ -58,35 +137,139 @@ pub struct ProcessNodeEvent;
 
 impl ChainableEvent for ProcessNodeEvent {
     fn execute(&self, context: &mut EventContext) -> EventResult<()> {
-        let queue: BinaryHeap<QueueNode> = match context.get("queue") {
+        let graph: Arc<Graph> = match context.get("graph") {
+            Some(g) => g,
+            None => return EventResult::Failure("Graph not found in context".to_string()),
+        };
+
+        // Move the queue out and update the state in place, so neither is
+        // cloned; the queue is put back below
+        let mut queue: BinaryHeap<QueueNode> = match context.take("queue") {
             Some(q) => q,
             None => return EventResult::Failure("Queue not found in context".to_string()),
         };
 
-        let mut state: DijkstraState = match context.get("state") {
+        let state: &mut DijkstraState = match context.get_mut("state") {
             Some(s) => s,
-            None => return EventResult::Failure("State not found in context".to_string()),
+            None => {
+                context.set("queue", queue);
+                return EventResult::Failure("State not found in context".to_string());
+            }
         };
 
+        // Stale entries are skipped within this event, as in
+        // ProcessBatchEvent, so each event settles one node while any remain
+        while let Some(QueueNode { node, distance }) = queue.pop() {
+            // Skip if already visited, if distance is stale, or if the node
+            // is out of range (only possible with BestEffort defaults)
+            if state.visited.get(node.0).copied().unwrap_or(true)
+                || distance > state.distances[node.0]
+            {
+                continue;
+            }
+
+            state.visited[node.0] = true;
+            state.nodes_expanded += 1;
+
+            // Process neighbors
+            for edge in &graph.adjacency_list[node.0] {
+                let new_distance = distance.saturating_add(edge.weight);
+
+                if new_distance < state.distances[edge.to.0] {
+                    state.distances[edge.to.0] = new_distance;
+                    state.predecessors[edge.to.0] = Some(node);
+
+                    queue.push(QueueNode {
+                        node: edge.to,
+                        distance: new_distance,
+                    });
+                } else if new_distance == state.distances[edge.to.0] {
+                    state.break_tie(node, edge.to);
+                }
+            }
+            break;
+        }
+
+        let more = !queue.is_empty();
+        context.set("queue", queue);
+        context.set("continue", more);
+        EventResult::Success(())
+    }
+
+    fn name(&self) -> &str {
+        "ProcessNode"
+    }
+
+    fn required_keys(&self) -> &[&str] {
+        &["queue", "state", "graph"]
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        &["queue", "continue"]
+    }
+
+    fn populate_defaults(&self, context: &mut EventContext) {
+        context.set("continue", false);
+    }
+
+    // Chains such as `dijkstra_eventchains_full` hold one of these per node
+    fn estimated_allocations(&self, _context: &EventContext) -> usize {
+        1
+    }
+}
+
+/// Event: Settle up to `batch_size` nodes from the priority queue
+pub struct ProcessBatchEvent {
+    batch_size: usize,
+}
+
+impl ProcessBatchEvent {
+    pub fn new(batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+        }
+    }
+}
+
+impl ChainableEvent for ProcessBatchEvent {
+    fn execute(&self, context: &mut EventContext) -> EventResult<()> {
         let graph: Arc<Graph> = match context.get("graph") {
             Some(g) => g,
             None => return EventResult::Failure("Graph not found in context".to_string()),
         };
 
-        let mut queue = queue; // Make queue mutable
+        // Move the queue out and update the state in place, so neither is
+        // cloned; the queue is put back below
+        let mut queue: BinaryHeap<QueueNode> = match context.take("queue") {
+            Some(q) => q,
+            None => return EventResult::Failure("Queue not found in context".to_string()),
+        };
 
-        if let Some(QueueNode { node, distance }) = queue.pop() {
-            // Skip if already visited or if distance is stale
-            if state.visited[node.0] || distance > state.distances[node.0] {
+        let state: &mut DijkstraState = match context.get_mut("state") {
+            Some(s) => s,
+            None => {
                 context.set("queue", queue);
-                context.set("state", state);
-                context.set("continue", true);
-                return EventResult::Success(());
+                return EventResult::Failure("State not found in context".to_string());
+            }
+        };
+
+        // Stale entries don't count toward the batch; only settled nodes do
+        let mut settled = 0;
+        while settled < self.batch_size {
+            let Some(QueueNode { node, distance }) = queue.pop() else {
+                break;
+            };
+
+            if state.visited.get(node.0).copied().unwrap_or(true)
+                || distance > state.distances[node.0]
+            {
+                continue;
             }
 
             state.visited[node.0] = true;
+            state.nodes_expanded += 1;
+            settled += 1;
 
-            // Process neighbors
             for edge in &graph.adjacency_list[node.0] {
                 let new_distance = distance.saturating_add(edge.weight);
 
@@ -98,21 +281,36 @@ impl ChainableEvent for ProcessNodeEvent {
                         node: edge.to,
                         distance: new_distance,
                     });
+                } else if new_distance == state.distances[edge.to.0] {
+                    state.break_tie(node, edge.to);
                 }
             }
-
-            context.set("continue", !queue.is_empty());
-        } else {
-            context.set("continue", false);
         }
 
+        let more = !queue.is_empty();
         context.set("queue", queue);
-        context.set("state", state);
+        context.set("continue", more);
         EventResult::Success(())
     }
 
     fn name(&self) -> &str {
-        "ProcessNode"
+        "ProcessBatch"
+    }
+
+    fn required_keys(&self) -> &[&str] {
+        &["queue", "state", "graph"]
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        &["queue", "continue"]
+    }
+
+    fn populate_defaults(&self, context: &mut EventContext) {
+        context.set("continue", false);
+    }
+
+    fn estimated_allocations(&self, _context: &EventContext) -> usize {
+        1
     }
 }
 
@@ -129,7 +327,7 @@ impl FinalizeResultEvent {
 
 impl ChainableEvent for FinalizeResultEvent {
     fn execute(&self, context: &mut EventContext) -> EventResult<()> {
-        let state: DijkstraState = match context.get("state") {
+        let state: &DijkstraState = match context.get_ref("state") {
             Some(s) => s,
             None => return EventResult::Failure("State not found in context".to_string()),
         };
@@ -140,7 +338,7 @@ impl ChainableEvent for FinalizeResultEvent {
         };
 
         let result =
-            crate::graph::ShortestPathResult::reconstruct_path(&state, source, self.target);
+            crate::graph::ShortestPathResult::reconstruct_path(state, source, self.target);
 
         context.set("result", result);
         EventResult::Success(())
@@ -149,4 +347,355 @@ impl ChainableEvent for FinalizeResultEvent {
     fn name(&self) -> &str {
         "FinalizeResult"
     }
+
+    fn required_keys(&self) -> &[&str] {
+        &["state", "source"]
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        &["result"]
+    }
+}
+
+/// Event: Finalize only the distance to the target, skipping path
+/// reconstruction
+pub struct FinalizeDistanceEvent {
+    target: NodeId,
+}
+
+impl FinalizeDistanceEvent {
+    pub fn new(target: NodeId) -> Self {
+        Self { target }
+    }
+}
+
+impl ChainableEvent for FinalizeDistanceEvent {
+    fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+        // Borrowed rather than cloned: only one distance is read
+        let state: &DijkstraState = match context.get_ref("state") {
+            Some(s) => s,
+            None => return EventResult::Failure("State not found in context".to_string()),
+        };
+
+        let distance = crate::graph::ShortestPathResult::distance_only(state, self.target);
+
+        context.set("distance", distance);
+        EventResult::Success(())
+    }
+
+    fn name(&self) -> &str {
+        "FinalizeDistance"
+    }
+
+    fn required_keys(&self) -> &[&str] {
+        &["state"]
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        &["distance"]
+    }
+}
+
+/// Event: Store distances to every node, not just one target
+pub struct FinalizeAllDistancesEvent;
+
+impl ChainableEvent for FinalizeAllDistancesEvent {
+    fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+        let state: &DijkstraState = match context.get_ref("state") {
+            Some(s) => s,
+            None => return EventResult::Failure("State not found in context".to_string()),
+        };
+
+        let source: NodeId = match context.get("source") {
+            Some(s) => s,
+            None => return EventResult::Failure("Source not found in context".to_string()),
+        };
+
+        context.set("all_distances", AllDistancesResult::from_state(state, source));
+        EventResult::Success(())
+    }
+
+    fn name(&self) -> &str {
+        "FinalizeAllDistances"
+    }
+
+    fn required_keys(&self) -> &[&str] {
+        &["state", "source"]
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        &["all_distances"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra_eventchains::dijkstra_eventchains_batched;
+    use crate::comprehensive_benchmarking::{bench_lock, run_comprehensive_benchmark};
+    use crate::eventchains::{
+        ChainStatus, ConditionalEvent, EventChain, FaultToleranceMode, RepeatUntilEvent, EXTERNAL_PROVENANCE,
+    };
+    use crate::tier_baselines::dijkstra_tier1_baseline;
+
+    fn finalize_when_state_computed(target: NodeId) -> Box<ConditionalEvent> {
+        Box::new(ConditionalEvent::new(
+            Box::new(|ctx: &EventContext| ctx.has("state")),
+            Box::new(FinalizeResultEvent::new(target)),
+        ))
+    }
+
+    #[test]
+    fn conditional_finalize_runs_once_state_is_computed() {
+        let mut chain = EventChain::new();
+        chain.add_event(Box::new(InitializeStateEvent::new(NodeId(0), 1)));
+        chain.add_event(finalize_when_state_computed(NodeId(0)));
+
+        let mut context = EventContext::new();
+        assert!(chain.execute(&mut context).success);
+        assert!(context.has("result"));
+    }
+
+    #[test]
+    fn conditional_finalize_is_skipped_without_failing() {
+        let mut chain = EventChain::new();
+        chain.add_event(finalize_when_state_computed(NodeId(0)));
+
+        let mut context = EventContext::new();
+        let result = chain.execute(&mut context);
+        assert!(result.success);
+        assert!(result.failures.is_empty());
+        assert!(!context.has("result"));
+    }
+
+    #[test]
+    fn provenance_attributes_each_key_to_its_writer() {
+        let mut chain = EventChain::new();
+        chain.add_event(Box::new(InitializeStateEvent::new(NodeId(0), 3)));
+        chain.add_event(Box::new(InitializePriorityQueueEvent));
+        chain.add_event(Box::new(FinalizeResultEvent::new(NodeId(0))));
+
+        let mut context = EventContext::with_provenance();
+        context.set("graph", Arc::new(Graph::new(3)));
+        assert!(chain.execute(&mut context).success);
+
+        let provenance = context.provenance();
+        let writer = |key: &str| {
+            provenance
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, event)| event.as_str())
+        };
+        assert_eq!(writer("graph"), Some(EXTERNAL_PROVENANCE));
+        assert_eq!(writer("state"), Some("InitializeState"));
+        assert_eq!(writer("queue"), Some("InitializePriorityQueue"));
+        assert_eq!(writer("result"), Some("FinalizeResult"));
+
+        context.clear();
+        assert_eq!(context.keys().count(), 0);
+        assert!(context.provenance().is_empty());
+    }
+
+    #[test]
+    fn provenance_is_not_tracked_by_default() {
+        let mut context = EventContext::new();
+        context.set("graph", Arc::new(Graph::new(1)));
+        assert!(context.provenance().is_empty());
+    }
+
+    #[test]
+    fn context_built_without_a_graph_fails_at_the_first_reader() {
+        let mut chain = EventChain::new();
+        chain.add_event(Box::new(InitializeStateEvent::new(NodeId(0), 3)));
+        chain.add_event(Box::new(InitializePriorityQueueEvent));
+        chain.add_event(Box::new(ProcessNodeEvent));
+
+        let mut context = EventContextBuilder::new().source(NodeId(0)).build();
+        let result = chain.execute(&mut context);
+
+        assert_eq!(result.status, ChainStatus::Failed);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].event_name, "ProcessNode");
+        assert_eq!(result.failures[0].error_message, "Graph not found in context");
+        // The queue's own dependency, the source, was there
+        assert!(context.has("queue"));
+    }
+
+    #[test]
+    fn chain_without_initialize_state_fails_validation() {
+        let mut chain = EventChain::new();
+        chain.add_event(Box::new(InitializePriorityQueueEvent));
+        chain.add_event(Box::new(ProcessNodeEvent));
+        chain.add_event(Box::new(FinalizeResultEvent::new(NodeId(2))));
+
+        let context = EventContextBuilder::new().graph(Arc::new(Graph::new(3))).build();
+        assert_eq!(
+            chain.validate_for(&context),
+            Err(vec![
+                "InitializePriorityQueue requires 'source', which no earlier event provides".to_string(),
+                "ProcessNode requires 'state', which no earlier event provides".to_string(),
+                // ProcessNode updates "state" in place rather than providing it
+                "FinalizeResult requires 'state', which no earlier event provides".to_string(),
+                "FinalizeResult requires 'source', which no earlier event provides".to_string(),
+            ])
+        );
+        // Without the context's keys, the graph is missing as well
+        let missing = chain.validate().unwrap_err();
+        assert!(missing.contains(&"ProcessNode requires 'graph', which no earlier event provides".to_string()));
+
+        let mut fixed = EventChain::new();
+        fixed.add_event(Box::new(InitializeStateEvent::new(NodeId(0), 3)));
+        fixed.add_event(Box::new(InitializePriorityQueueEvent));
+        fixed.add_event(Box::new(ProcessNodeEvent));
+        fixed.add_event(Box::new(FinalizeResultEvent::new(NodeId(2))));
+        assert_eq!(fixed.validate_for(&context), Ok(()));
+    }
+
+    #[test]
+    fn batch_event_settles_at_most_batch_size_nodes() {
+        let graph = Arc::new(Graph::random_connected_seeded(100, 400, 100, 2));
+        let mut context = EventContextBuilder::new().graph(graph).build();
+        assert!(InitializeStateEvent::new(NodeId(0), 100).execute(&mut context).is_success());
+        assert!(InitializePriorityQueueEvent.execute(&mut context).is_success());
+
+        let batch = ProcessBatchEvent::new(16);
+        assert!(batch.execute(&mut context).is_success());
+        let state: DijkstraState = context.get("state").unwrap();
+        assert_eq!(state.nodes_expanded, 16);
+        assert_eq!(context.get::<bool>("continue"), Some(true));
+
+        // Draining takes the remaining 84 nodes in six more batches
+        let mut events = 1;
+        while context.get::<bool>("continue") == Some(true) {
+            assert!(batch.execute(&mut context).is_success());
+            events += 1;
+        }
+        let state: DijkstraState = context.get("state").unwrap();
+        assert_eq!(state.nodes_expanded, 100);
+        assert_eq!(events, 7);
+    }
+
+    #[test]
+    fn batched_search_matches_the_baseline_at_every_batch_size() {
+        let graph = Arc::new(Graph::random_connected_seeded(150, 600, 100, 9));
+        let expected = dijkstra_tier1_baseline(graph.clone(), NodeId(3), NodeId(140));
+        // 0 is clamped to 1
+        for batch_size in [0, 1, 4, 16, 64, 1000] {
+            let result = dijkstra_eventchains_batched(graph.clone(), NodeId(3), NodeId(140), batch_size);
+            assert_eq!(result.distance, expected.distance, "batch size {}", batch_size);
+            assert_eq!(result.path, expected.path, "batch size {}", batch_size);
+        }
+    }
+
+    /// `ProcessNodeEvent` as it was before moving values out of the
+    /// context: clones the queue and state in, and sets both back
+    struct CloningProcessNodeEvent;
+
+    impl ChainableEvent for CloningProcessNodeEvent {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            let mut queue: BinaryHeap<QueueNode> = context.get("queue").unwrap();
+            let mut state: DijkstraState = context.get("state").unwrap();
+            let graph: Arc<Graph> = context.get("graph").unwrap();
+
+            while let Some(QueueNode { node, distance }) = queue.pop() {
+                if state.visited[node.0] || distance > state.distances[node.0] {
+                    continue;
+                }
+                state.visited[node.0] = true;
+                for edge in &graph.adjacency_list[node.0] {
+                    let new_distance = distance.saturating_add(edge.weight);
+                    if new_distance < state.distances[edge.to.0] {
+                        state.distances[edge.to.0] = new_distance;
+                        state.predecessors[edge.to.0] = Some(node);
+                        queue.push(QueueNode {
+                            node: edge.to,
+                            distance: new_distance,
+                        });
+                    }
+                }
+                break;
+            }
+
+            context.set("continue", !queue.is_empty());
+            context.set("queue", queue);
+            context.set("state", state);
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "ProcessNode"
+        }
+    }
+
+    fn allocations_per_query(graph: &Arc<Graph>, process: fn() -> Box<dyn ChainableEvent>) -> f64 {
+        let metrics = run_comprehensive_benchmark(10, || {
+            let mut chain = EventChain::new();
+            chain.add_event(Box::new(InitializeStateEvent::new(NodeId(0), graph.nodes)));
+            chain.add_event(Box::new(InitializePriorityQueueEvent));
+            chain.add_event(Box::new(RepeatUntilEvent::new(
+                process(),
+                Box::new(|ctx: &EventContext| ctx.get::<bool>("continue").unwrap_or(true)),
+            )));
+            let mut context = EventContextBuilder::new().graph(graph.clone()).build();
+            if chain.execute(&mut context).success {
+                Ok(())
+            } else {
+                Err("chain failed".to_string())
+            }
+        });
+        metrics.allocations_per_run
+    }
+
+    #[test]
+    fn process_node_moves_state_instead_of_cloning_it() {
+        let _lock = bench_lock();
+        let graph = Arc::new(Graph::random_connected_seeded(200, 800, 100, 1));
+
+        let moved = allocations_per_query(&graph, || Box::new(ProcessNodeEvent));
+        let cloned = allocations_per_query(&graph, || Box::new(CloningProcessNodeEvent));
+
+        // Cloning copies the heap and three state vectors on every node
+        assert!(moved * 2.0 < cloned, "{} vs {}", moved, cloned);
+    }
+
+    #[test]
+    fn failing_process_node_chain_reserves_its_failures_up_front() {
+        // Without a graph in the context every ProcessNodeEvent fails
+        let mut chain = EventChain::new().with_fault_tolerance(FaultToleranceMode::Lenient);
+        for _ in 0..1000 {
+            chain.add_event(Box::new(ProcessNodeEvent));
+        }
+
+        let result = chain.execute(&mut EventContext::new());
+        assert_eq!(result.status, ChainStatus::CompletedWithWarnings);
+        assert_eq!(result.failures.len(), 1000);
+        // Growing by doubling would have left room for 1024
+        assert_eq!(result.failures.capacity(), 1000);
+
+        let (result, timings) = chain.execute_timed(&mut EventContext::new());
+        assert_eq!(result.failures.capacity(), 1000);
+        assert_eq!((timings.len(), timings.capacity()), (1000, 1000));
+
+        // A strict chain stops at its first failure, so it reserves one
+        let strict = chain.with_fault_tolerance(FaultToleranceMode::Strict);
+        let result = strict.execute(&mut EventContext::new());
+        assert_eq!(result.status, ChainStatus::Failed);
+        assert_eq!(result.failures.capacity(), 1);
+    }
+
+    #[test]
+    fn failed_chain_context_keeps_only_the_completed_events_keys() {
+        // No graph in the context, so the second event fails
+        let mut chain = EventChain::new();
+        chain.add_event(Box::new(InitializeStateEvent::new(NodeId(0), 4)));
+        chain.add_event(Box::new(ProcessNodeEvent));
+        chain.add_event(Box::new(FinalizeResultEvent::new(NodeId(3))));
+
+        let mut context = EventContext::new();
+        let (result, context) = chain.execute_with_context(&mut context);
+        assert_eq!(result.status, ChainStatus::Failed);
+        assert_eq!(result.failures[0].event_name, "ProcessNode");
+        assert!(context.has("state") && context.has("source"));
+        assert!(!context.has("result"));
+    }
 }
@@ -22,6 +22,7 @@ pub fn dijkstra_traditional(
         }
 
         state.visited[node.0] = true;
+        state.nodes_expanded += 1;
 
         if node == target {
             break;
@@ -38,6 +39,8 @@ pub fn dijkstra_traditional(
                     node: edge.to,
                     distance: new_distance,
                 });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
             }
         }
     }
@@ -85,6 +88,7 @@ pub fn dijkstra_traditional_logged(
         }
 
         state.visited[node.0] = true;
+        state.nodes_expanded += 1;
         nodes_processed += 1;
 
         if node == target {
@@ -102,6 +106,8 @@ pub fn dijkstra_traditional_logged(
                     node: edge.to,
                     distance: new_distance,
                 });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
             }
         }
     }
@@ -122,3 +128,25 @@ pub fn dijkstra_traditional_logged(
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tier_baselines::dijkstra_tier1_baseline;
+
+    #[test]
+    fn expansions_match_the_tier1_baseline() {
+        let graph = Arc::new(Graph::random_connected_seeded(200, 800, 100, 11));
+        for target in [0, 1, 57, 199] {
+            let (source, target) = (NodeId(3), NodeId(target));
+            let expected = dijkstra_tier1_baseline(graph.clone(), source, target);
+
+            let plain = dijkstra_traditional(graph.clone(), source, target);
+            let logged = dijkstra_traditional_logged(graph.clone(), source, target, false);
+            for result in [plain, logged] {
+                assert_eq!(result.nodes_expanded, expected.nodes_expanded, "target {}", target.0);
+                assert_eq!(result.path, expected.path, "target {}", target.0);
+            }
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use hashbrown::HashMap;
 use std::any::Any;
 use std::fmt;
+use std::time::{Duration, Instant};
 
 /// Result of an event execution
 #[derive(Debug, Clone)]
 pub enum EventResult<T> {
     Success(T),
     Failure(String),
+    /// Stop the whole chain, whatever its `FaultToleranceMode`. Events
+    /// after this one never run. Counts as a failure for `is_failure`.
+    Abort(String),
 }
 
 impl<T> EventResult<T> {
@@ -15,38 +19,155 @@ impl<T> EventResult<T> {
     }
 
     pub fn is_failure(&self) -> bool {
-        matches!(self, EventResult::Failure(_))
+        matches!(self, EventResult::Failure(_) | EventResult::Abort(_))
+    }
+
+    pub fn is_abort(&self) -> bool {
+        matches!(self, EventResult::Abort(_))
     }
 
     pub fn get_data(self) -> Option<T> {
         match self {
             EventResult::Success(data) => Some(data),
-            EventResult::Failure(_) => None,
+            EventResult::Failure(_) | EventResult::Abort(_) => None,
         }
     }
 
     pub fn get_error(&self) -> Option<&str> {
         match self {
             EventResult::Success(_) => None,
-            EventResult::Failure(msg) => Some(msg),
+            EventResult::Failure(msg) | EventResult::Abort(msg) => Some(msg),
+        }
+    }
+
+    // The combinators below mirror `Result`. `Failure` and `Abort` pass
+    // through unchanged, so an abort is never downgraded to a failure.
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> EventResult<U> {
+        match self {
+            EventResult::Success(data) => EventResult::Success(f(data)),
+            EventResult::Failure(msg) => EventResult::Failure(msg),
+            EventResult::Abort(msg) => EventResult::Abort(msg),
+        }
+    }
+
+    pub fn and_then<U, F: FnOnce(T) -> EventResult<U>>(self, f: F) -> EventResult<U> {
+        match self {
+            EventResult::Success(data) => f(data),
+            EventResult::Failure(msg) => EventResult::Failure(msg),
+            EventResult::Abort(msg) => EventResult::Abort(msg),
+        }
+    }
+
+    /// Rewrite the error message of a `Failure` or `Abort`, keeping the variant
+    pub fn map_err<F: FnOnce(String) -> String>(self, f: F) -> EventResult<T> {
+        match self {
+            EventResult::Success(data) => EventResult::Success(data),
+            EventResult::Failure(msg) => EventResult::Failure(f(msg)),
+            EventResult::Abort(msg) => EventResult::Abort(f(msg)),
         }
     }
+
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            EventResult::Success(data) => data,
+            EventResult::Failure(_) | EventResult::Abort(_) => default,
+        }
+    }
+
+    /// Convert to a `Result` so `?` works in event bodies. `Abort` becomes
+    /// a plain `Err`; the abort/failure distinction does not survive a round
+    /// trip.
+    pub fn into_result(self) -> Result<T, String> {
+        match self {
+            EventResult::Success(data) => Ok(data),
+            EventResult::Failure(msg) | EventResult::Abort(msg) => Err(msg),
+        }
+    }
+
+    /// `Ok` becomes `Success` and `Err` becomes `Failure`
+    pub fn from_result(result: Result<T, String>) -> Self {
+        match result {
+            Ok(data) => EventResult::Success(data),
+            Err(msg) => EventResult::Failure(msg),
+        }
+    }
+}
+
+impl<T> From<EventResult<T>> for Result<T, String> {
+    fn from(result: EventResult<T>) -> Self {
+        result.into_result()
+    }
 }
 
+impl<T> From<Result<T, String>> for EventResult<T> {
+    fn from(result: Result<T, String>) -> Self {
+        EventResult::from_result(result)
+    }
+}
+
+/// Writer recorded for keys set outside any event
+pub const EXTERNAL_PROVENANCE: &str = "external";
+
 /// Context that flows through the event chain
 pub struct EventContext {
     data: HashMap<String, Box<dyn Any + Send + Sync>>,
+    // Last writer per key; only tracked when created via `with_provenance`
+    provenance: Option<HashMap<String, String>>,
+    current_event: Option<String>,
 }
 
 impl EventContext {
     pub fn new() -> Self {
         Self {
             data: HashMap::new(),
+            provenance: None,
+            current_event: None,
+        }
+    }
+
+    /// Create a context that records which event last wrote each key.
+    /// Keys written by middleware are attributed to the event they wrap.
+    #[allow(dead_code, reason = "provenance is a debugging aid; the driver never records it")]
+    pub fn with_provenance() -> Self {
+        Self {
+            provenance: Some(HashMap::new()),
+            ..Self::new()
         }
     }
 
     pub fn set<T: Any + Send + Sync>(&mut self, key: &str, value: T) {
         self.data.insert(key.to_string(), Box::new(value));
+        if let Some(provenance) = &mut self.provenance {
+            let writer = self.current_event.as_deref().unwrap_or(EXTERNAL_PROVENANCE);
+            provenance.insert(key.to_string(), writer.to_string());
+        }
+    }
+
+    /// Mark `event_name` as the writer of subsequent `set` calls.
+    /// No-op unless provenance tracking is enabled.
+    pub fn enter_event(&mut self, event_name: &str) {
+        if self.provenance.is_some() {
+            self.current_event = Some(event_name.to_string());
+        }
+    }
+
+    pub fn exit_event(&mut self) {
+        self.current_event = None;
+    }
+
+    /// (key, writer event) pairs sorted by key; empty unless the context was
+    /// created with `with_provenance`
+    #[allow(dead_code, reason = "provenance is a debugging aid; the driver never records it")]
+    pub fn provenance(&self) -> Vec<(String, String)> {
+        let mut entries: Vec<(String, String)> = self
+            .provenance
+            .iter()
+            .flatten()
+            .map(|(key, event)| (key.clone(), event.clone()))
+            .collect();
+        entries.sort();
+        entries
     }
 
     pub fn get<T: Any + Send + Sync + Clone>(&self, key: &str) -> Option<T> {
@@ -55,9 +176,68 @@ impl EventContext {
             .and_then(|boxed| boxed.downcast_ref::<T>().cloned())
     }
 
+    /// Shared access to a value in place, without cloning it
+    pub fn get_ref<T: Any + Send + Sync>(&self, key: &str) -> Option<&T> {
+        self.data.get(key).and_then(|boxed| boxed.downcast_ref::<T>())
+    }
+
+    /// Mutable access to a value in place, without cloning it
+    pub fn get_mut<T: Any + Send + Sync>(&mut self, key: &str) -> Option<&mut T> {
+        self.data
+            .get_mut(key)
+            .and_then(|boxed| boxed.downcast_mut::<T>())
+    }
+
+    /// Move a value out of the context without cloning it. A value of a
+    /// different type is left in place and `None` is returned.
+    pub fn take<T: Any + Send + Sync>(&mut self, key: &str) -> Option<T> {
+        let boxed = self.data.remove(key)?;
+        match boxed.downcast::<T>() {
+            Ok(value) => Some(*value),
+            Err(boxed) => {
+                self.data.insert(key.to_string(), boxed);
+                None
+            }
+        }
+    }
+
+    /// Drop every stored value (and recorded provenance) while keeping the
+    /// map's allocated capacity, so one context can be reused across runs.
+    /// Everything the caller set up front, such as "graph", is dropped too
+    /// and must be set again before the next run.
+    #[allow(dead_code, reason = "for reusing one context across runs; the driver builds a fresh one per run")]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        if let Some(provenance) = &mut self.provenance {
+            provenance.clear();
+        }
+        self.current_event = None;
+    }
+
+    #[allow(dead_code, reason = "events read keys through `get`; only tests check presence")]
     pub fn has(&self, key: &str) -> bool {
         self.data.contains_key(key)
     }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.data.keys().map(String::as_str)
+    }
+
+    /// Per-event durations recorded by `TimingMiddleware`, as
+    /// (event name, nanoseconds) pairs sorted by event name
+    pub fn collected_timings(&self) -> Vec<(String, u64)> {
+        let mut timings: Vec<(String, u64)> = self
+            .data
+            .iter()
+            .filter_map(|(key, value)| {
+                let name = key.strip_suffix("_duration_ns")?;
+                let nanos = value.downcast_ref::<u64>()?;
+                Some((name.to_string(), *nanos))
+            })
+            .collect();
+        timings.sort();
+        timings
+    }
 }
 
 impl Default for EventContext {
@@ -70,6 +250,133 @@ impl Default for EventContext {
 pub trait ChainableEvent: Send + Sync {
     fn execute(&self, context: &mut EventContext) -> EventResult<()>;
     fn name(&self) -> &str;
+
+    /// Write fallback values for the context keys this event would have
+    /// produced. Called by `BestEffort` chains after this event fails, so
+    /// downstream events can still run.
+    fn populate_defaults(&self, _context: &mut EventContext) {}
+
+    /// Context keys this event reads, which an earlier event (or the
+    /// caller) must set. Checked by `EventChain::validate`.
+    fn required_keys(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Context keys this event sets on success
+    fn provided_keys(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Failure records the chain should reserve room for on this event's
+    /// behalf, typically 1 for an event that can fail. Before running, the
+    /// chain sizes its failures vector to the sum over its events (at most
+    /// one under `Strict`, which stops at the first failure), and
+    /// `execute_timed` its timings to at least that, so a chain whose events
+    /// keep failing never regrows either.
+    fn estimated_allocations(&self, _context: &EventContext) -> usize {
+        0
+    }
+}
+
+/// Event that runs `inner` only when `predicate` holds for the current
+/// context; otherwise it is a successful no-op
+pub struct ConditionalEvent {
+    predicate: Box<dyn Fn(&EventContext) -> bool + Send + Sync>,
+    inner: Box<dyn ChainableEvent>,
+}
+
+impl ConditionalEvent {
+    pub fn new(
+        predicate: Box<dyn Fn(&EventContext) -> bool + Send + Sync>,
+        inner: Box<dyn ChainableEvent>,
+    ) -> Self {
+        Self { predicate, inner }
+    }
+}
+
+impl ChainableEvent for ConditionalEvent {
+    fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+        if (self.predicate)(context) {
+            self.inner.execute(context)
+        } else {
+            EventResult::Success(())
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn populate_defaults(&self, context: &mut EventContext) {
+        self.inner.populate_defaults(context)
+    }
+
+    // Validation assumes the predicate holds, so `inner`'s keys count as
+    // provided
+    fn required_keys(&self) -> &[&str] {
+        self.inner.required_keys()
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        self.inner.provided_keys()
+    }
+
+    fn estimated_allocations(&self, context: &EventContext) -> usize {
+        self.inner.estimated_allocations(context)
+    }
+}
+
+/// Event that executes `body` repeatedly while `should_continue` holds,
+/// letting a single chain step drive an inherently iterative algorithm.
+/// The predicate is checked before each iteration; the first failing
+/// iteration stops the loop and is returned as this event's result.
+pub struct RepeatUntilEvent {
+    body: Box<dyn ChainableEvent>,
+    should_continue: Box<dyn Fn(&EventContext) -> bool + Send + Sync>,
+}
+
+impl RepeatUntilEvent {
+    pub fn new(
+        body: Box<dyn ChainableEvent>,
+        should_continue: Box<dyn Fn(&EventContext) -> bool + Send + Sync>,
+    ) -> Self {
+        Self {
+            body,
+            should_continue,
+        }
+    }
+}
+
+impl ChainableEvent for RepeatUntilEvent {
+    fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+        while (self.should_continue)(context) {
+            let result = self.body.execute(context);
+            if result.is_failure() {
+                return result;
+            }
+        }
+        EventResult::Success(())
+    }
+
+    fn name(&self) -> &str {
+        self.body.name()
+    }
+
+    fn populate_defaults(&self, context: &mut EventContext) {
+        self.body.populate_defaults(context)
+    }
+
+    fn required_keys(&self) -> &[&str] {
+        self.body.required_keys()
+    }
+
+    fn provided_keys(&self) -> &[&str] {
+        self.body.provided_keys()
+    }
+
+    fn estimated_allocations(&self, context: &EventContext) -> usize {
+        self.body.estimated_allocations(context)
+    }
 }
 
 /// Trait for middleware
@@ -87,6 +394,7 @@ pub trait EventMiddleware: Send + Sync {
 pub struct EventFailure {
     pub event_name: String,
     pub error_message: String,
+    #[allow(dead_code, reason = "kept for consumers ordering failures; the report lists them as recorded")]
     pub timestamp: u64,
 }
 
@@ -116,6 +424,8 @@ pub enum ChainStatus {
     Completed,
     CompletedWithWarnings,
     Failed,
+    /// An event or middleware returned `EventResult::Abort`
+    Aborted,
 }
 
 impl ChainResult {
@@ -142,21 +452,73 @@ impl ChainResult {
             status: ChainStatus::Failed,
         }
     }
+
+    pub fn aborted(failures: Vec<EventFailure>) -> Self {
+        Self {
+            success: false,
+            failures,
+            status: ChainStatus::Aborted,
+        }
+    }
 }
 
 /// Fault tolerance mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FaultToleranceMode {
+    /// Abort at the first failure
     Strict,
+    /// Continue past failures; the chain completes with warnings
     Lenient,
+    /// Like `Lenient`, but a failed event's `populate_defaults` runs so that
+    /// events depending on its output can still execute
     BestEffort,
 }
 
+/// How each event is threaded through the middleware stack. Both
+/// strategies run middleware in the same LIFO order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DispatchStrategy {
+    /// Recurse through the middleware for every event, building a fresh
+    /// `next` closure at each layer
+    #[default]
+    Recursive,
+    /// Compose the middleware into a single pipeline once per `execute`
+    /// and run every event through it
+    Iterative,
+}
+
+/// Where `EventChain::use_middleware_with_priority` places a middleware in
+/// the stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiddlewarePriority {
+    /// Wrap every other middleware, including ones registered later. Among
+    /// several `Outermost` middleware the last registered is outermost.
+    Outermost,
+    /// Run closest to the event, inside every other middleware, including
+    /// ones registered later. Among several `Innermost` middleware the last
+    /// registered is innermost.
+    Innermost,
+    /// Position in the stack counted outward from the event (0 is
+    /// innermost), as for `EventChain::insert_middleware`
+    At(usize),
+}
+
+/// Middleware stack composed by `DispatchStrategy::Iterative`, taking the
+/// event to run at its core
+type MiddlewarePipeline<'a> =
+    Box<dyn FnMut(&dyn ChainableEvent, &mut EventContext) -> EventResult<()> + 'a>;
+
 /// Main EventChain orchestrator
 pub struct EventChain {
     events: Vec<Box<dyn ChainableEvent>>,
+    // Innermost first, so the last entry runs first. The first
+    // `pinned_innermost` and last `pinned_outermost` entries were registered
+    // with those priorities and stay at the ends.
     middlewares: Vec<Box<dyn EventMiddleware>>,
+    pinned_innermost: usize,
+    pinned_outermost: usize,
     fault_tolerance: FaultToleranceMode,
+    dispatch: DispatchStrategy,
 }
 
 impl EventChain {
@@ -164,7 +526,10 @@ impl EventChain {
         Self {
             events: Vec::new(),
             middlewares: Vec::new(),
+            pinned_innermost: 0,
+            pinned_outermost: 0,
             fault_tolerance: FaultToleranceMode::Strict,
+            dispatch: DispatchStrategy::Recursive,
         }
     }
 
@@ -173,22 +538,153 @@ impl EventChain {
         self
     }
 
+    pub fn with_dispatch(mut self, dispatch: DispatchStrategy) -> Self {
+        self.dispatch = dispatch;
+        self
+    }
+
     pub fn add_event(&mut self, event: Box<dyn ChainableEvent>) -> &mut Self {
         self.events.push(event);
         self
     }
 
+    /// Register middleware around every event. Dispatch is LIFO: the last
+    /// middleware registered is outermost, running first before the event
+    /// and last after it. Middleware pinned with
+    /// `MiddlewarePriority::Outermost` still wraps it.
     pub fn use_middleware(&mut self, middleware: Box<dyn EventMiddleware>) -> &mut Self {
-        self.middlewares.push(middleware);
+        let index = self.middlewares.len() - self.pinned_outermost;
+        self.middlewares.insert(index, middleware);
         self
     }
 
+    /// Insert middleware at `index` in the stack, counted outward from the
+    /// event: 0 runs innermost and `middleware_count()` outermost. The index
+    /// is clamped so it never lands inside the middleware pinned with
+    /// `MiddlewarePriority::Innermost` or `Outermost`.
+    pub fn insert_middleware(&mut self, index: usize, middleware: Box<dyn EventMiddleware>) -> &mut Self {
+        let index = index.clamp(
+            self.pinned_innermost,
+            self.middlewares.len() - self.pinned_outermost,
+        );
+        self.middlewares.insert(index, middleware);
+        self
+    }
+
+    /// Register middleware at a position that doesn't depend on the order
+    /// of the other registrations, e.g. logging that must always run
+    /// outermost
+    pub fn use_middleware_with_priority(
+        &mut self,
+        middleware: Box<dyn EventMiddleware>,
+        priority: MiddlewarePriority,
+    ) -> &mut Self {
+        match priority {
+            MiddlewarePriority::Outermost => {
+                self.middlewares.push(middleware);
+                self.pinned_outermost += 1;
+            }
+            MiddlewarePriority::Innermost => {
+                self.middlewares.insert(0, middleware);
+                self.pinned_innermost += 1;
+            }
+            MiddlewarePriority::At(index) => {
+                self.insert_middleware(index, middleware);
+            }
+        }
+        self
+    }
+
+    #[allow(dead_code, reason = "only tests inspect the stack size")]
+    pub fn middleware_count(&self) -> usize {
+        self.middlewares.len()
+    }
+
+    /// Check without executing that every event's `required_keys` are
+    /// provided by an earlier event, returning one message per missing
+    /// dependency. Keys the caller sets up front (such as "graph") count as
+    /// missing here; use `validate_for` to include them.
+    #[allow(dead_code, reason = "the driver validates against its context with `validate_for`")]
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        self.validate_keys(Vec::new())
+    }
+
+    /// Like `validate`, but keys already present in `context` count as
+    /// provided
+    pub fn validate_for(&self, context: &EventContext) -> Result<(), Vec<String>> {
+        self.validate_keys(context.keys().collect())
+    }
+
+    fn validate_keys<'a>(&'a self, mut provided: Vec<&'a str>) -> Result<(), Vec<String>> {
+        let mut missing = Vec::new();
+
+        for event in &self.events {
+            for key in event.required_keys() {
+                if !provided.contains(key) {
+                    missing.push(format!(
+                        "{} requires '{}', which no earlier event provides",
+                        event.name(),
+                        key
+                    ));
+                }
+            }
+            provided.extend(event.provided_keys());
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
     pub fn execute(&self, context: &mut EventContext) -> ChainResult {
-        let mut failures = Vec::new();
+        let mut pipeline = self.compose_middleware();
+        self.execute_events(context, |event, context| {
+            self.execute_with_middleware(pipeline.as_mut(), event, context)
+        })
+    }
+
+    /// Like `execute`, but hands the context back alongside the result so a
+    /// failed run can be inspected: keys set by the events that completed
+    /// before the failure are still present, and those of later events are
+    /// not
+    #[allow(dead_code, reason = "debugging entry point for inspecting a failed run; not needed by the driver")]
+    pub fn execute_with_context<'c>(
+        &self,
+        context: &'c mut EventContext,
+    ) -> (ChainResult, &'c EventContext) {
+        let result = self.execute(context);
+        (result, context)
+    }
+
+    /// Like `execute`, but also returns each executed event's wall time
+    /// (including its middleware), measured inline by the executor rather
+    /// than by a `TimingMiddleware`
+    #[allow(dead_code, reason = "lower-overhead profiling entry point; the driver times events with `TimingMiddleware`")]
+    pub fn execute_timed(&self, context: &mut EventContext) -> (ChainResult, Vec<(String, Duration)>) {
+        let mut timings = Vec::with_capacity(self.events.len().max(self.estimated_records(context)));
+        let mut pipeline = self.compose_middleware();
+        let result = self.execute_events(context, |event, context| {
+            let start = Instant::now();
+            let result = self.execute_with_middleware(pipeline.as_mut(), event, context);
+            timings.push((event.name().to_string(), start.elapsed()));
+            result
+        });
+        (result, timings)
+    }
+
+    fn execute_events<F>(&self, context: &mut EventContext, mut run: F) -> ChainResult
+    where
+        F: FnMut(&dyn ChainableEvent, &mut EventContext) -> EventResult<()>,
+    {
+        let mut failures = Vec::with_capacity(self.estimated_records(context));
 
         for event in &self.events {
             // Build middleware pipeline (LIFO - last registered executes first)
-            let result = self.execute_with_middleware(event.as_ref(), context);
+            context.enter_event(event.name());
+            let result = run(event.as_ref(), context);
+            context.exit_event();
 
             if result.is_failure() {
                 let failure = EventFailure::new(
@@ -197,14 +693,24 @@ impl EventChain {
                 );
                 failures.push(failure);
 
+                if result.is_abort() {
+                    return ChainResult::aborted(failures);
+                }
+
                 match self.fault_tolerance {
                     FaultToleranceMode::Strict => {
                         return ChainResult::failure(failures);
                     }
-                    FaultToleranceMode::Lenient | FaultToleranceMode::BestEffort => {
+                    FaultToleranceMode::Lenient => {
                         // Continue execution
                         continue;
                     }
+                    FaultToleranceMode::BestEffort => {
+                        context.enter_event(event.name());
+                        event.populate_defaults(context);
+                        context.exit_event();
+                        continue;
+                    }
                 }
             }
         }
@@ -216,8 +722,45 @@ impl EventChain {
         }
     }
 
+    /// Records to reserve for one run, from the events' `estimated_allocations`
+    fn estimated_records(&self, context: &EventContext) -> usize {
+        let expected: usize = self
+            .events
+            .iter()
+            .map(|event| event.estimated_allocations(context))
+            .sum();
+        if self.fault_tolerance == FaultToleranceMode::Strict {
+            expected.min(1)
+        } else {
+            expected
+        }
+    }
+
+    /// The composed pipeline for `DispatchStrategy::Iterative`; `None` for
+    /// recursive dispatch or when there is no middleware to compose
+    fn compose_middleware(&self) -> Option<MiddlewarePipeline<'_>> {
+        if self.dispatch != DispatchStrategy::Iterative || self.middlewares.is_empty() {
+            return None;
+        }
+
+        // Wrap from the inside out: the first registered middleware sits
+        // directly around the event, the last registered is outermost
+        let mut pipeline: MiddlewarePipeline<'_> =
+            Box::new(|event: &dyn ChainableEvent, context: &mut EventContext| event.execute(context));
+        for middleware in &self.middlewares {
+            let mut inner = pipeline;
+            pipeline = Box::new(move |event: &dyn ChainableEvent, context: &mut EventContext| {
+                let mut next = |ctx: &mut EventContext| inner(event, ctx);
+                middleware.execute(event, context, &mut next)
+            });
+        }
+
+        Some(pipeline)
+    }
+
     fn execute_with_middleware(
         &self,
+        pipeline: Option<&mut MiddlewarePipeline<'_>>,
         event: &dyn ChainableEvent,
         context: &mut EventContext,
     ) -> EventResult<()> {
@@ -225,8 +768,19 @@ impl EventChain {
             return event.execute(context);
         }
 
-        // Execute middleware in reverse order by recursively building the call stack
-        self.execute_middleware_recursive(0, event, context)
+        match pipeline {
+            Some(pipeline) => Self::execute_middleware_iterative(pipeline, event, context),
+            // Execute middleware in reverse order by recursively building the call stack
+            None => self.execute_middleware_recursive(0, event, context),
+        }
+    }
+
+    fn execute_middleware_iterative(
+        pipeline: &mut MiddlewarePipeline<'_>,
+        event: &dyn ChainableEvent,
+        context: &mut EventContext,
+    ) -> EventResult<()> {
+        pipeline(event, context)
     }
 
     fn execute_middleware_recursive(
@@ -266,6 +820,157 @@ impl fmt::Display for ChainStatus {
             ChainStatus::Completed => write!(f, "COMPLETED"),
             ChainStatus::CompletedWithWarnings => write!(f, "COMPLETED_WITH_WARNINGS"),
             ChainStatus::Failed => write!(f, "FAILED"),
+            ChainStatus::Aborted => write!(f, "ABORTED"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Busy-waits for a fixed time, so event durations dominate the chain's
+    struct SpinEvent(Duration);
+
+    impl ChainableEvent for SpinEvent {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            let start = Instant::now();
+            while start.elapsed() < self.0 {}
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "Spin"
+        }
+    }
+
+    #[test]
+    fn event_timings_add_up_to_the_chain_duration() {
+        let mut chain = EventChain::new();
+        for _ in 0..4 {
+            chain.add_event(Box::new(SpinEvent(Duration::from_millis(2))));
+        }
+
+        let start = Instant::now();
+        let (result, timings) = chain.execute_timed(&mut EventContext::new());
+        let total = start.elapsed();
+
+        assert!(result.success);
+        assert_eq!(timings.len(), 4);
+        let sum: Duration = timings.iter().map(|(_, duration)| *duration).sum();
+        assert!(sum <= total, "{:?} > {:?}", sum, total);
+        assert!(total - sum < total / 20, "{:?} of {:?} untimed", total - sum, total);
+    }
+
+    /// Sets its own name as a context key when it runs
+    struct MarkEvent(&'static str);
+
+    impl ChainableEvent for MarkEvent {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            context.set(self.0, true);
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            self.0
         }
     }
+
+    /// Aborts the chain instead of running the named event
+    struct AbortBefore(&'static str);
+
+    impl EventMiddleware for AbortBefore {
+        fn execute(
+            &self,
+            event: &dyn ChainableEvent,
+            context: &mut EventContext,
+            next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+        ) -> EventResult<()> {
+            if event.name() == self.0 {
+                EventResult::Abort("budget exceeded".to_string())
+            } else {
+                next(context)
+            }
+        }
+    }
+
+    #[test]
+    fn middleware_abort_stops_the_chain_in_every_mode() {
+        for mode in [FaultToleranceMode::Strict, FaultToleranceMode::Lenient, FaultToleranceMode::BestEffort] {
+            let mut chain = EventChain::new().with_fault_tolerance(mode);
+            chain.use_middleware(Box::new(AbortBefore("second")));
+            for name in ["first", "second", "third", "fourth"] {
+                chain.add_event(Box::new(MarkEvent(name)));
+            }
+
+            let mut context = EventContext::new();
+            let result = chain.execute(&mut context);
+
+            assert_eq!(result.status, ChainStatus::Aborted, "{:?}", mode);
+            assert_eq!(result.failures.len(), 1);
+            assert_eq!(result.failures[0].event_name, "second");
+            assert!(context.has("first"));
+            assert!(!context.has("third") && !context.has("fourth"), "{:?}", mode);
+        }
+    }
+
+    fn failure<T>() -> EventResult<T> {
+        EventResult::Failure("no graph".to_string())
+    }
+
+    #[test]
+    fn combinators_transform_successes_and_pass_failures_through() {
+        assert!(matches!(EventResult::Success(2).map(|x| x * 10), EventResult::Success(20)));
+        assert!(matches!(failure::<i32>().map(|x| x * 10), EventResult::Failure(msg) if msg == "no graph"));
+
+        let halve = |x: i32| {
+            if x % 2 == 0 {
+                EventResult::Success(x / 2)
+            } else {
+                EventResult::Failure(format!("{} is odd", x))
+            }
+        };
+        assert!(matches!(EventResult::Success(8).and_then(halve), EventResult::Success(4)));
+        assert!(matches!(EventResult::Success(3).and_then(halve), EventResult::Failure(msg) if msg == "3 is odd"));
+        assert!(matches!(failure::<i32>().and_then(halve), EventResult::Failure(msg) if msg == "no graph"));
+
+        let context = |msg: String| format!("InitializeState: {}", msg);
+        assert!(matches!(EventResult::Success(1).map_err(context), EventResult::Success(1)));
+        assert!(matches!(failure::<i32>().map_err(context), EventResult::Failure(msg) if msg == "InitializeState: no graph"));
+
+        assert_eq!(EventResult::Success(5).unwrap_or(0), 5);
+        assert_eq!(failure::<i32>().unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn combinators_never_downgrade_an_abort() {
+        let abort = || EventResult::<i32>::Abort("stop".to_string());
+        assert!(abort().map(|x| x + 1).is_abort());
+        assert!(abort().and_then(EventResult::Success).is_abort());
+        assert!(matches!(abort().map_err(|msg| msg.to_uppercase()), EventResult::Abort(msg) if msg == "STOP"));
+        assert_eq!(abort().unwrap_or(7), 7);
+    }
+
+    #[test]
+    fn results_round_trip_through_std_result() {
+        let ok: Result<u32, String> = EventResult::Success(3).into();
+        assert_eq!(ok, Ok(3));
+        let err: Result<u32, String> = failure().into();
+        assert_eq!(err, Err("no graph".to_string()));
+        assert_eq!(EventResult::<u32>::Abort("stop".to_string()).into_result(), Err("stop".to_string()));
+
+        assert!(matches!(EventResult::from(ok), EventResult::Success(3)));
+        assert!(matches!(EventResult::from_result(err), EventResult::Failure(msg) if msg == "no graph"));
+    }
+
+    #[test]
+    fn question_mark_works_inside_a_result_returning_helper() {
+        fn doubled(result: EventResult<u32>) -> Result<u32, String> {
+            let value = result.into_result()?;
+            Ok(value * 2)
+        }
+
+        assert!(matches!(EventResult::from(doubled(EventResult::Success(4))), EventResult::Success(8)));
+        assert!(matches!(EventResult::from(doubled(failure())), EventResult::Failure(msg) if msg == "no graph"));
+    }
 }
@@ -0,0 +1,169 @@
+use crate::graph::{forward_path_order, Graph, NodeId, ShortestPathResult};
+use std::cmp::Ordering;
+
+// ============================================================================
+// Compile-Time-Sized State
+// ============================================================================
+//
+// For small graphs with a node count known at compile time, the search
+// state lives in fixed arrays on the stack and the next node is chosen by a
+// linear scan instead of a heap, so a query makes no allocations at all.
+// The O(N²) scan only pays off while N is small.
+
+/// `DijkstraState` with `[_; N]` arrays instead of vectors. Graphs may have
+/// up to `N` nodes; slots past the graph's node count start out settled so
+/// the search never selects them.
+#[derive(Debug, Clone)]
+pub struct FixedDijkstraState<const N: usize> {
+    pub distances: [u32; N],
+    pub predecessors: [Option<NodeId>; N],
+    pub visited: [bool; N],
+    pub nodes_expanded: usize,
+}
+
+impl<const N: usize> FixedDijkstraState<N> {
+    pub fn new(nodes: usize, source: NodeId) -> Self {
+        assert!(nodes <= N, "{} nodes do not fit a state sized for {}", nodes, N);
+
+        let mut state = Self {
+            distances: [u32::MAX; N],
+            predecessors: [None; N],
+            visited: [false; N],
+            nodes_expanded: 0,
+        };
+        state.distances[source.0] = 0;
+        state.visited[nodes..].fill(true);
+        state
+    }
+
+    /// Same rule as `DijkstraState::break_tie`, so paths match the
+    /// vector-based searches
+    #[inline]
+    fn break_tie(&mut self, from: NodeId, to: NodeId) {
+        if self.visited[to.0] {
+            return;
+        }
+        if let Some(current) = self.predecessors[to.0] {
+            if forward_path_order(&self.predecessors, from, current, to) == Ordering::Less {
+                self.predecessors[to.0] = Some(from);
+            }
+        }
+    }
+
+    /// Unsettled node with the smallest finite distance, lowest id first
+    #[inline]
+    fn closest_unsettled(&self) -> Option<NodeId> {
+        let mut closest = None;
+        let mut best = u32::MAX;
+        for node in 0..N {
+            if !self.visited[node] && self.distances[node] < best {
+                best = self.distances[node];
+                closest = Some(NodeId(node));
+            }
+        }
+        closest
+    }
+
+    /// Distance to `target`; `None` if the search never reached it
+    pub fn distance(&self, target: NodeId) -> Option<u32> {
+        match self.distances.get(target.0) {
+            Some(&distance) if distance != u32::MAX => Some(distance),
+            _ => None,
+        }
+    }
+
+    /// The finished search as a `ShortestPathResult`. This allocates the
+    /// path, so keep it out of allocation-sensitive code.
+    pub fn to_result(&self, source: NodeId, target: NodeId) -> ShortestPathResult {
+        let distance = self.distance(target);
+
+        let mut path = Vec::new();
+        if distance.is_some() {
+            let mut current = target;
+            path.push(current);
+            while let Some(pred) = self.predecessors[current.0].filter(|_| current != source) {
+                path.push(pred);
+                current = pred;
+            }
+            path.reverse();
+        }
+
+        ShortestPathResult {
+            source,
+            target,
+            distance,
+            path,
+            reached_from: None,
+            nodes_expanded: self.nodes_expanded,
+            overflowed: false,
+        }
+    }
+}
+
+/// Tier 1 baseline over a `FixedDijkstraState<N>` with a linear-scan
+/// frontier. Makes no heap allocations; distances are identical to
+/// `dijkstra_tier1_baseline` for any graph of at most `N` nodes.
+pub fn dijkstra_fixed_baseline<const N: usize>(
+    graph: &Graph,
+    source: NodeId,
+    target: NodeId,
+) -> FixedDijkstraState<N> {
+    let mut state = FixedDijkstraState::<N>::new(graph.nodes, source);
+
+    while let Some(node) = state.closest_unsettled() {
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        let distance = state.distances[node.0];
+        for edge in &graph.adjacency_list[node.0] {
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comprehensive_benchmarking::{bench_lock, run_comprehensive_benchmark};
+    use crate::tier_baselines::dijkstra_tier1_baseline;
+    use std::sync::Arc;
+
+    #[test]
+    fn sixteen_node_state_matches_the_dynamic_search() {
+        // A full graph and one leaving spare slots
+        for nodes in [16, 11] {
+            let graph = Arc::new(Graph::random_connected_seeded(nodes, nodes * 3, 20, 14));
+            for target in 0..nodes {
+                let (source, target) = (NodeId(2), NodeId(target));
+                let fixed = dijkstra_fixed_baseline::<16>(&graph, source, target).to_result(source, target);
+                let dynamic = dijkstra_tier1_baseline(graph.clone(), source, target);
+                assert_eq!(fixed.distance, dynamic.distance, "{} nodes, target {:?}", nodes, target);
+                assert_eq!(fixed.path, dynamic.path, "{} nodes, target {:?}", nodes, target);
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_search_makes_no_allocations() {
+        let _lock = bench_lock();
+        let graph = Graph::random_connected_seeded(16, 48, 20, 14);
+        let metrics = run_comprehensive_benchmark(10, || {
+            std::hint::black_box(dijkstra_fixed_baseline::<16>(&graph, NodeId(0), NodeId(15)));
+            Ok(())
+        });
+        assert_eq!(metrics.allocations_per_run, 0.0);
+    }
+}
@@ -1,11 +1,18 @@
+use hashbrown::HashMap;
 use std::cmp::Ordering;
+use std::fmt;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
 
 /// Node in the graph
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeId(pub usize);
 
 /// Edge with weight
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Edge {
     pub to: NodeId,
     pub weight: u32,
@@ -13,6 +20,7 @@ pub struct Edge {
 
 /// Graph representation
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Graph {
     pub nodes: usize,
     pub adjacency_list: Vec<Vec<Edge>>,
@@ -35,18 +43,83 @@ impl Graph {
         self.add_edge(to, from, weight);
     }
 
+    /// Set the weight of edge `from -> to`, returning its previous weight.
+    /// If a `to -> from` edge has that same previous weight (as
+    /// `add_bidirectional_edge` creates), it is updated too, keeping
+    /// bidirectional graphs symmetric. With parallel edges only the first is
+    /// changed.
+    pub fn update_edge_weight(&mut self, from: NodeId, to: NodeId, new_weight: u32) -> Result<u32, GraphError> {
+        for node in [from, to] {
+            if node.0 >= self.nodes {
+                return Err(GraphError::NodeOutOfRange { node, nodes: self.nodes });
+            }
+        }
+
+        let edge = self.adjacency_list[from.0]
+            .iter_mut()
+            .find(|edge| edge.to == to)
+            .ok_or(GraphError::EdgeNotFound { from, to })?;
+        let old_weight = std::mem::replace(&mut edge.weight, new_weight);
+
+        if from != to {
+            if let Some(reverse) = self.adjacency_list[to.0]
+                .iter_mut()
+                .find(|edge| edge.to == from && edge.weight == old_weight)
+            {
+                reverse.weight = new_weight;
+            }
+        }
+
+        Ok(old_weight)
+    }
+
+    /// Collapse parallel edges: each node keeps one edge per distinct
+    /// neighbor, at the minimum weight among its duplicates. Shortest-path
+    /// distances are unchanged. Surviving edges stay in the order of each
+    /// neighbor's first occurrence.
+    #[allow(dead_code, reason = "generators never emit parallel edges; for graphs loaded from elsewhere")]
+    pub fn dedup_edges(&mut self) {
+        let mut slots: HashMap<NodeId, usize> = HashMap::new();
+        for edges in &mut self.adjacency_list {
+            slots.clear();
+            let mut kept: Vec<Edge> = Vec::with_capacity(edges.len());
+            for edge in edges.drain(..) {
+                match slots.get(&edge.to) {
+                    Some(&slot) => kept[slot].weight = kept[slot].weight.min(edge.weight),
+                    None => {
+                        slots.insert(edge.to, kept.len());
+                        kept.push(edge);
+                    }
+                }
+            }
+            *edges = kept;
+        }
+    }
+
     /// Generate a random connected graph
     pub fn random_connected(nodes: usize, edges: usize, max_weight: u32) -> Self {
+        Self::random_connected_seeded(nodes, edges, max_weight, DEFAULT_SEED)
+    }
+
+    /// Generate a random connected graph from an explicit RNG seed
+    pub fn random_connected_seeded(nodes: usize, edges: usize, max_weight: u32, seed: u64) -> Self {
+        Self::random_with_weights(nodes, edges, WeightDistribution::Uniform { max_weight }, seed)
+    }
+
+    /// Like `random_connected_seeded`, drawing every edge weight from
+    /// `weights`. `Uniform` produces exactly the graph
+    /// `random_connected_seeded` does for the same seed.
+    pub fn random_with_weights(nodes: usize, edges: usize, weights: WeightDistribution, seed: u64) -> Self {
         use hashbrown::HashSet;
 
         let mut graph = Graph::new(nodes);
-        let mut rng = SimpleRng::new(12345);
+        let mut rng = SimpleRng::new(seed);
         let mut edge_set = HashSet::new();
 
         // Ensure connectivity by creating a spanning tree
         for i in 1..nodes {
             let parent = rng.next_usize() % i;
-            let weight = (rng.next_usize() % max_weight as usize) as u32 + 1;
+            let weight = weights.sample(&mut rng);
             graph.add_bidirectional_edge(NodeId(parent), NodeId(i), weight);
             edge_set.insert((parent.min(i), parent.max(i)));
         }
@@ -61,7 +134,7 @@ impl Graph {
             if from != to {
                 let edge_key = (from.min(to), from.max(to));
                 if edge_set.insert(edge_key) {
-                    let weight = (rng.next_usize() % max_weight as usize) as u32 + 1;
+                    let weight = weights.sample(&mut rng);
                     graph.add_bidirectional_edge(NodeId(from), NodeId(to), weight);
                     added += 1;
                 }
@@ -71,6 +144,582 @@ impl Graph {
 
         graph
     }
+
+    /// Generate a random connected graph whose mean degree is about
+    /// `avg_degree`, i.e. `nodes * avg_degree / 2` undirected edges, so
+    /// density stays constant as `nodes` grows. The spanning tree always
+    /// comes first, so degrees below 2 still yield `nodes - 1` edges.
+    #[allow(dead_code, reason = "test cases give an edge count; this is for ad-hoc density sweeps")]
+    pub fn random_with_avg_degree(nodes: usize, avg_degree: f64, max_weight: u32, seed: u64) -> Self {
+        let edges = (nodes as f64 * avg_degree / 2.0).round() as usize;
+        Self::random_connected_seeded(nodes, edges, max_weight, seed)
+    }
+
+    /// Generate a `rows` x `cols` grid where each cell connects to its
+    /// 4-neighbors. Cell `(r, c)` is `NodeId(r * cols + c)`; weights are drawn
+    /// uniformly from `edge_weight_range`.
+    pub fn grid(rows: usize, cols: usize, edge_weight_range: RangeInclusive<u32>) -> Self {
+        let mut graph = Graph::new(rows * cols);
+        let mut rng = SimpleRng::new(DEFAULT_SEED);
+        let (low, high) = (*edge_weight_range.start(), *edge_weight_range.end());
+        assert!(low <= high, "empty edge weight range");
+        let span = (high - low) as usize + 1;
+
+        for r in 0..rows {
+            for c in 0..cols {
+                let node = NodeId(r * cols + c);
+                if c + 1 < cols {
+                    let weight = low + (rng.next_usize() % span) as u32;
+                    graph.add_bidirectional_edge(node, NodeId(node.0 + 1), weight);
+                }
+                if r + 1 < rows {
+                    let weight = low + (rng.next_usize() % span) as u32;
+                    graph.add_bidirectional_edge(node, NodeId(node.0 + cols), weight);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Generate a scale-free graph by preferential attachment. Starts from
+    /// `m` fully connected nodes, then attaches each new node to `m` distinct
+    /// existing nodes chosen with probability proportional to their degree.
+    /// Edge weights are drawn from `1..=SCALE_FREE_MAX_WEIGHT`.
+    pub fn barabasi_albert(nodes: usize, m: usize, seed: u64) -> Self {
+        assert!(m >= 1, "each new node must attach to at least one node");
+
+        let mut graph = Graph::new(nodes);
+        let mut rng = SimpleRng::new(seed);
+        let seed_nodes = m.min(nodes);
+        // Every edge endpoint appears once, so uniform sampling from this
+        // list is sampling proportional to degree
+        let mut endpoints = Vec::new();
+
+        for i in 0..seed_nodes {
+            for j in (i + 1)..seed_nodes {
+                let weight = (rng.next_usize() % SCALE_FREE_MAX_WEIGHT as usize) as u32 + 1;
+                graph.add_bidirectional_edge(NodeId(i), NodeId(j), weight);
+                endpoints.push(i);
+                endpoints.push(j);
+            }
+        }
+
+        let mut targets = Vec::with_capacity(m);
+        for new_node in seed_nodes..nodes {
+            targets.clear();
+            let wanted = m.min(new_node);
+            while targets.len() < wanted {
+                // A single seed node has no edges yet; fall back to uniform
+                let candidate = if endpoints.is_empty() {
+                    rng.next_usize() % new_node
+                } else {
+                    endpoints[rng.next_usize() % endpoints.len()]
+                };
+                if !targets.contains(&candidate) {
+                    targets.push(candidate);
+                }
+            }
+
+            for &target in &targets {
+                let weight = (rng.next_usize() % SCALE_FREE_MAX_WEIGHT as usize) as u32 + 1;
+                graph.add_bidirectional_edge(NodeId(new_node), NodeId(target), weight);
+                endpoints.push(new_node);
+                endpoints.push(target);
+            }
+        }
+
+        graph
+    }
+
+    /// Parse a DIMACS shortest-path (`.gr`) graph: one `p sp <n> <m>`
+    /// problem line followed by `m` directed `a <from> <to> <weight>` arcs
+    /// with 1-indexed nodes. Lines starting with `c` are comments.
+    pub fn from_dimacs(reader: impl BufRead) -> Result<Graph, ParseError> {
+        let mut graph: Option<Graph> = None;
+        let mut expected_arcs = 0;
+        let mut arcs = 0;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line_number = index + 1;
+            let error = |message: String| ParseError {
+                line: line_number,
+                message,
+            };
+
+            let line = line.map_err(|err| error(err.to_string()))?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            match fields.as_slice() {
+                [] => {}
+                [first, ..] if first.starts_with('c') => {}
+                ["p", "sp", nodes, edges] => {
+                    if graph.is_some() {
+                        return Err(error("duplicate problem line".to_string()));
+                    }
+                    let nodes = parse_field(nodes, "node count").map_err(error)?;
+                    expected_arcs = parse_field(edges, "arc count").map_err(error)?;
+                    graph = Some(Graph::new(nodes));
+                }
+                ["a", from, to, weight] => {
+                    let graph = graph
+                        .as_mut()
+                        .ok_or_else(|| error("arc before problem line".to_string()))?;
+                    let from = parse_node(from, graph.nodes).map_err(error)?;
+                    let to = parse_node(to, graph.nodes).map_err(error)?;
+                    let weight = parse_field(weight, "weight").map_err(error)?;
+                    graph.add_edge(from, to, weight);
+                    arcs += 1;
+                }
+                _ => return Err(error(format!("unrecognized line '{}'", line))),
+            }
+        }
+
+        let graph = graph.ok_or(ParseError {
+            line: 0,
+            message: "missing problem line".to_string(),
+        })?;
+        if arcs != expected_arcs {
+            return Err(ParseError {
+                line: 0,
+                message: format!("problem line declares {} arcs but {} were given", expected_arcs, arcs),
+            });
+        }
+
+        Ok(graph)
+    }
+
+    /// Build a directed graph from a dense weight matrix: `matrix[i][j]` is
+    /// the weight of edge i -> j, and cells equal to `no_edge` have no edge.
+    /// Diagonal cells are treated like any other, so a zero diagonal becomes
+    /// zero-weight self-loops. Errors report the 1-based row that breaks
+    /// squareness.
+    #[allow(dead_code, reason = "the driver loads edge lists; matrices are for hand-written fixtures")]
+    pub fn from_adjacency_matrix(matrix: &[Vec<u32>], no_edge: u32) -> Result<Graph, ParseError> {
+        let nodes = matrix.len();
+        let mut graph = Graph::new(nodes);
+
+        for (from, row) in matrix.iter().enumerate() {
+            if row.len() != nodes {
+                return Err(ParseError {
+                    line: from + 1,
+                    message: format!("row has {} columns, expected {}", row.len(), nodes),
+                });
+            }
+            for (to, &weight) in row.iter().enumerate() {
+                if weight != no_edge {
+                    graph.add_edge(NodeId(from), NodeId(to), weight);
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Serialize as `{"nodes": n, "adjacency_list": [[{"to": id, "weight": w}, ...], ...]}`,
+    /// preserving adjacency order
+    #[cfg(feature = "serde")]
+    #[allow(dead_code, reason = "library API for persisting graphs; the driver regenerates them from seeds")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("graph serialization cannot fail")
+    }
+
+    /// Inverse of `to_json`; also rejects graphs whose adjacency list length
+    /// or edge targets disagree with `nodes`
+    #[cfg(feature = "serde")]
+    #[allow(dead_code, reason = "library API for persisting graphs; the driver regenerates them from seeds")]
+    pub fn from_json(json: &str) -> Result<Graph, ParseError> {
+        let graph: Graph = serde_json::from_str(json).map_err(|err| ParseError {
+            line: err.line(),
+            message: err.to_string(),
+        })?;
+
+        let invalid = |message: String| ParseError { line: 0, message };
+        if graph.adjacency_list.len() != graph.nodes {
+            return Err(invalid(format!(
+                "adjacency list has {} entries for {} nodes",
+                graph.adjacency_list.len(),
+                graph.nodes
+            )));
+        }
+        if let Some(edge) = graph.adjacency_list.iter().flatten().find(|e| e.to.0 >= graph.nodes) {
+            return Err(invalid(format!(
+                "edge target {} out of range for {} nodes",
+                edge.to.0, graph.nodes
+            )));
+        }
+
+        Ok(graph)
+    }
+
+    /// Graph with every edge flipped, keeping weights. For graphs built from
+    /// bidirectional edges this has the same edges as `self`.
+    pub fn reverse(&self) -> Graph {
+        let mut reversed = Graph::new(self.nodes);
+        for (from, edges) in self.adjacency_list.iter().enumerate() {
+            for edge in edges {
+                reversed.add_edge(edge.to, NodeId(from), edge.weight);
+            }
+        }
+        reversed
+    }
+
+    /// Consume the graph into its read-only `FrozenGraph` form, searched by
+    /// `dijkstra_frozen_baseline`. Unlike `CsrGraph::from(&graph)`, the edges
+    /// are moved rather than cloned.
+    pub fn freeze(self) -> FrozenGraph {
+        let mut offsets = Vec::with_capacity(self.nodes + 1);
+        let mut edges = Vec::with_capacity(self.adjacency_list.iter().map(Vec::len).sum());
+
+        offsets.push(0);
+        for neighbors in self.adjacency_list {
+            edges.extend(neighbors);
+            offsets.push(edges.len());
+        }
+
+        FrozenGraph(CsrGraph {
+            nodes: self.nodes,
+            offsets,
+            edges,
+        })
+    }
+
+    /// Structural summary of the graph; connectivity is checked by BFS from
+    /// node 0, so for directed graphs it means "all reachable from node 0"
+    pub fn stats(&self) -> GraphStats {
+        let degrees = self.adjacency_list.iter().map(Vec::len);
+        let edges: usize = degrees.clone().sum();
+
+        GraphStats {
+            nodes: self.nodes,
+            edges,
+            min_degree: degrees.clone().min().unwrap_or(0),
+            max_degree: degrees.max().unwrap_or(0),
+            mean_degree: if self.nodes == 0 {
+                0.0
+            } else {
+                edges as f64 / self.nodes as f64
+            },
+            connected: self.nodes == 0
+                || self.hop_distances(NodeId(0)).iter().all(|&hops| hops != usize::MAX),
+        }
+    }
+
+    /// Component label per node, treating every edge as undirected. Labels
+    /// are `0..component_count`, numbered in order of each component's
+    /// lowest node, so node 0 is always in component 0.
+    #[allow(dead_code, reason = "the driver generates connected graphs; for checking loaded ones")]
+    pub fn connected_components(&self) -> Vec<usize> {
+        // Union-find with path halving; the smaller root becomes the parent
+        // so each root is its component's lowest node
+        fn find(parents: &mut [usize], mut node: usize) -> usize {
+            while parents[node] != node {
+                parents[node] = parents[parents[node]];
+                node = parents[node];
+            }
+            node
+        }
+
+        let mut parents: Vec<usize> = (0..self.nodes).collect();
+        for (from, edges) in self.adjacency_list.iter().enumerate() {
+            for edge in edges {
+                let (a, b) = (find(&mut parents, from), find(&mut parents, edge.to.0));
+                if a != b {
+                    parents[a.max(b)] = a.min(b);
+                }
+            }
+        }
+
+        let mut labels = vec![usize::MAX; self.nodes];
+        let mut count = 0;
+        for node in 0..self.nodes {
+            let root = find(&mut parents, node);
+            if labels[root] == usize::MAX {
+                labels[root] = count;
+                count += 1;
+            }
+            labels[node] = labels[root];
+        }
+        labels
+    }
+
+    /// True when every node is in one component of `connected_components`.
+    /// An empty graph counts as connected.
+    #[allow(dead_code, reason = "the driver generates connected graphs; for checking loaded ones")]
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().iter().all(|&label| label == 0)
+    }
+
+    /// Hop counts from `source` via breadth-first search, ignoring weights.
+    /// Unreachable nodes are `usize::MAX`.
+    pub fn hop_distances(&self, source: NodeId) -> Vec<usize> {
+        use std::collections::VecDeque;
+
+        let mut hops = vec![usize::MAX; self.nodes];
+        let mut frontier = VecDeque::new();
+        hops[source.0] = 0;
+        frontier.push_back(source);
+
+        while let Some(node) = frontier.pop_front() {
+            for edge in &self.adjacency_list[node.0] {
+                if hops[edge.to.0] == usize::MAX {
+                    hops[edge.to.0] = hops[node.0] + 1;
+                    frontier.push_back(edge.to);
+                }
+            }
+        }
+
+        hops
+    }
+}
+
+/// Structural properties of a `Graph`, from `Graph::stats`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub nodes: usize,
+    /// Directed adjacency entries; a bidirectional edge counts twice
+    pub edges: usize,
+    pub min_degree: usize,
+    pub max_degree: usize,
+    pub mean_degree: f64,
+    pub connected: bool,
+}
+
+/// Compressed-sparse-row form of a `Graph`: the neighbors of node `n` are
+/// `edges[offsets[n]..offsets[n + 1]]`, stored in one contiguous buffer
+#[derive(Debug, Clone)]
+pub struct CsrGraph {
+    pub nodes: usize,
+    pub offsets: Vec<usize>,
+    pub edges: Vec<Edge>,
+}
+
+impl CsrGraph {
+    pub fn neighbors(&self, node: NodeId) -> &[Edge] {
+        &self.edges[self.offsets[node.0]..self.offsets[node.0 + 1]]
+    }
+}
+
+impl From<&Graph> for CsrGraph {
+    fn from(graph: &Graph) -> Self {
+        let mut offsets = Vec::with_capacity(graph.nodes + 1);
+        let mut edges = Vec::with_capacity(graph.adjacency_list.iter().map(Vec::len).sum());
+
+        offsets.push(0);
+        for neighbors in &graph.adjacency_list {
+            edges.extend(neighbors.iter().cloned());
+            offsets.push(edges.len());
+        }
+
+        Self {
+            nodes: graph.nodes,
+            offsets,
+            edges,
+        }
+    }
+}
+
+/// Immutable graph from `Graph::freeze`, for running many queries on a graph
+/// that no longer changes; stored in `CsrGraph` layout
+#[derive(Debug, Clone)]
+pub struct FrozenGraph(CsrGraph);
+
+impl FrozenGraph {
+    pub fn as_csr(&self) -> &CsrGraph {
+        &self.0
+    }
+}
+
+/// Error from modifying or querying a graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    NodeOutOfRange { node: NodeId, nodes: usize },
+    EdgeNotFound { from: NodeId, to: NodeId },
+    #[allow(dead_code, reason = "only `LabeledGraph` reports unknown names")]
+    UnknownName(String),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::NodeOutOfRange { node, nodes } => {
+                write!(f, "node {} out of range for {} nodes", node.0, nodes)
+            }
+            GraphError::EdgeNotFound { from, to } => {
+                write!(f, "no edge from node {} to node {}", from.0, to.0)
+            }
+            GraphError::UnknownName(name) => write!(f, "no node named '{}'", name),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// Error from parsing a graph file. `line` is 1-based, or 0 for errors
+/// about the file as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn parse_field<T: std::str::FromStr>(field: &str, what: &str) -> Result<T, String> {
+    field
+        .parse()
+        .map_err(|_| format!("invalid {} '{}'", what, field))
+}
+
+/// Convert a 1-indexed DIMACS node to a `NodeId`
+fn parse_node(field: &str, nodes: usize) -> Result<NodeId, String> {
+    let node: usize = parse_field(field, "node")?;
+    if node == 0 || node > nodes {
+        return Err(format!("node {} out of range 1..={}", node, nodes));
+    }
+    Ok(NodeId(node - 1))
+}
+
+/// Order of the forward paths source -> `a` -> `to` and source -> `b` -> `to`
+/// in the shortest-path tree `predecessors`, without materializing either.
+/// The paths agree up to the nearest common ancestor of `a` and `b` and are
+/// decided by the nodes that follow it; `to` counts as the node after an
+/// endpoint. Roots (nodes without a predecessor) are compared directly.
+pub(crate) fn forward_path_order(predecessors: &[Option<NodeId>], a: NodeId, b: NodeId, to: NodeId) -> Ordering {
+    let depth = |mut node: NodeId| {
+        let mut depth = 0;
+        while let Some(pred) = predecessors[node.0] {
+            node = pred;
+            depth += 1;
+        }
+        depth
+    };
+
+    // Lift the deeper node to the other's depth, remembering the node that
+    // follows it on its path
+    let (mut a, mut b) = (a, b);
+    let (mut after_a, mut after_b) = (to, to);
+    let (mut depth_a, mut depth_b) = (depth(a), depth(b));
+    while depth_a > depth_b {
+        after_a = a;
+        a = predecessors[a.0].unwrap();
+        depth_a -= 1;
+    }
+    while depth_b > depth_a {
+        after_b = b;
+        b = predecessors[b.0].unwrap();
+        depth_b -= 1;
+    }
+    if a == b {
+        return after_a.0.cmp(&after_b.0);
+    }
+
+    // Same depth: climb together until the parents agree (or both are roots)
+    loop {
+        let (pred_a, pred_b) = (predecessors[a.0], predecessors[b.0]);
+        match (pred_a, pred_b) {
+            (Some(pa), Some(pb)) if pa != pb => {
+                a = pa;
+                b = pb;
+            }
+            _ => return a.0.cmp(&b.0),
+        }
+    }
+}
+
+/// Seed used by `Graph::random_connected`
+pub const DEFAULT_SEED: u64 = 12345;
+
+/// Upper bound on edge weights produced by `Graph::barabasi_albert`
+pub const SCALE_FREE_MAX_WEIGHT: u32 = 100;
+
+/// How `Graph::random_with_weights` draws edge weights. Every weight is at
+/// least 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeightDistribution {
+    /// Uniform in `1..=max_weight`, as `Graph::random_connected` uses
+    Uniform { max_weight: u32 },
+    /// `1 + floor(X)` with `X` exponential at rate `lambda`: mostly cheap
+    /// edges with a long tail of expensive ones; the mean is about
+    /// `1 + 1 / lambda`
+    #[allow(dead_code, reason = "the driver benchmarks uniform weights; skewed ones are for ad-hoc studies")]
+    Exponential { lambda: f64 },
+    /// `high` with probability `p_high`, otherwise `low`, e.g. local roads
+    /// versus a few congested links
+    #[allow(dead_code, reason = "the driver benchmarks uniform weights; skewed ones are for ad-hoc studies")]
+    Bimodal { low: u32, high: u32, p_high: f64 },
+}
+
+impl WeightDistribution {
+    pub fn sample(&self, rng: &mut SimpleRng) -> u32 {
+        match *self {
+            WeightDistribution::Uniform { max_weight } => {
+                (rng.next_usize() % max_weight as usize) as u32 + 1
+            }
+            WeightDistribution::Exponential { lambda } => {
+                // 1 - U lies in (0, 1], so the logarithm is finite; the cast
+                // saturates for extreme draws at tiny rates
+                let x = -(1.0 - rng.next_f64()).ln() / lambda;
+                (x as u32).saturating_add(1)
+            }
+            WeightDistribution::Bimodal { low, high, p_high } => {
+                let weight = if rng.next_f64() < p_high { high } else { low };
+                weight.max(1)
+            }
+        }
+    }
+}
+
+/// Memoizes generated graphs by `(nodes, edges, seed)` so that tiers and
+/// test cases within one benchmark session share a single instance
+pub struct GraphCache {
+    max_weight: u32,
+    graphs: HashMap<(usize, usize, u64), Arc<Graph>>,
+}
+
+impl GraphCache {
+    pub fn new(max_weight: u32) -> Self {
+        Self {
+            max_weight,
+            graphs: HashMap::new(),
+        }
+    }
+
+    /// Return the cached graph for these parameters, generating it on first use
+    pub fn get_or_generate(&mut self, nodes: usize, edges: usize, seed: u64) -> Arc<Graph> {
+        let max_weight = self.max_weight;
+        self.graphs
+            .entry((nodes, edges, seed))
+            .or_insert_with(|| {
+                Arc::new(Graph::random_connected_seeded(nodes, edges, max_weight, seed))
+            })
+            .clone()
+    }
+
+    /// Cache a graph that was loaded rather than generated, so that
+    /// `get_or_generate` with the same parameters returns it
+    pub fn insert(&mut self, nodes: usize, edges: usize, seed: u64, graph: Arc<Graph>) {
+        self.graphs.insert((nodes, edges, seed), graph);
+    }
+
+    pub fn len(&self) -> usize {
+        self.graphs.len()
+    }
+
+    #[allow(dead_code, reason = "pairs with `len`; the driver only reads the size")]
+    pub fn is_empty(&self) -> bool {
+        self.graphs.is_empty()
+    }
 }
 
 /// Simple RNG for reproducible results
@@ -90,6 +739,11 @@ impl SimpleRng {
             .wrapping_add(1442695040888963407);
         (self.state >> 32) as usize
     }
+
+    /// Uniform in `[0, 1)`, from the same 32 bits as `next_usize`
+    pub fn next_f64(&mut self) -> f64 {
+        self.next_usize() as f64 / (1u64 << 32) as f64
+    }
 }
 
 /// State for Dijkstra's algorithm
@@ -98,6 +752,8 @@ pub struct DijkstraState {
     pub distances: Vec<u32>,
     pub predecessors: Vec<Option<NodeId>>,
     pub visited: Vec<bool>,
+    /// Nodes finalized so far; incremented wherever `visited` is set
+    pub nodes_expanded: usize,
 }
 
 impl DijkstraState {
@@ -109,6 +765,71 @@ impl DijkstraState {
             distances,
             predecessors: vec![None; nodes],
             visited: vec![false; nodes],
+            nodes_expanded: 0,
+        }
+    }
+
+    /// State for a search that only needs distances. `predecessors` is left
+    /// empty rather than allocated, so the search must not record
+    /// predecessors or break ties, and the answer is read with
+    /// `ShortestPathResult::distance_only`.
+    pub fn distance_only(nodes: usize, source: NodeId) -> Self {
+        let mut distances = vec![u32::MAX; nodes];
+        distances[source.0] = 0;
+
+        Self {
+            distances,
+            predecessors: Vec::new(),
+            visited: vec![false; nodes],
+            nodes_expanded: 0,
+        }
+    }
+
+    /// State in which no node is reachable: every distance is infinite and
+    /// every node already settled, so a search over it finds nothing
+    pub fn unreachable(nodes: usize) -> Self {
+        Self {
+            distances: vec![u32::MAX; nodes],
+            predecessors: vec![None; nodes],
+            visited: vec![true; nodes],
+            nodes_expanded: 0,
+        }
+    }
+
+    /// Tie-break for a relaxation that reaches unsettled `to` from `from`
+    /// at exactly its current distance: keep whichever predecessor gives
+    /// the lexicographically smaller path read forward from the source.
+    ///
+    /// With positive weights every tight predecessor of a node is settled,
+    /// and its own path final, before the node itself, so each node ends up
+    /// with the predecessor of its forward-smallest shortest path whatever
+    /// order the queue pops equal-distance nodes in. The reconstructed path
+    /// is therefore canonical: of all shortest paths, the lexicographically
+    /// smallest node sequence from source to target.
+    #[inline]
+    pub fn break_tie(&mut self, from: NodeId, to: NodeId) {
+        if self.visited[to.0] {
+            return;
+        }
+        if let Some(current) = self.predecessors[to.0] {
+            if forward_path_order(&self.predecessors, from, current, to) == Ordering::Less {
+                self.predecessors[to.0] = Some(from);
+            }
+        }
+    }
+
+    /// State with every node in `sources` at distance 0
+    pub fn new_multi_source(nodes: usize, sources: &[NodeId]) -> Self {
+        let mut distances = vec![u32::MAX; nodes];
+        for source in sources {
+            distances[source.0] = 0;
+        }
+
+        Self {
+            distances,
+            predecessors: vec![None; nodes],
+            visited: vec![false; nodes],
+            nodes_expanded: 0,
         }
     }
 }
@@ -136,6 +857,46 @@ impl PartialOrd for QueueNode {
     }
 }
 
+/// Reason a `ShortestPathResult` is inconsistent with its graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// `distance` is `Some` but `path` is empty, or vice versa
+    PathDistanceMismatch { distance: Option<u32>, path_len: usize },
+    /// `path` does not start at `source` or end at `target`
+    WrongEndpoints { first: NodeId, last: NodeId },
+    /// Consecutive path nodes with no edge between them
+    MissingEdge { from: NodeId, to: NodeId },
+    /// Sum of edge weights along `path` differs from `distance`
+    WrongDistance { reported: u32, actual: u32 },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::PathDistanceMismatch { distance, path_len } => write!(
+                f,
+                "distance {:?} is inconsistent with a path of {} nodes",
+                distance, path_len
+            ),
+            PathError::WrongEndpoints { first, last } => write!(
+                f,
+                "path runs from node {} to node {} instead of source to target",
+                first.0, last.0
+            ),
+            PathError::MissingEdge { from, to } => {
+                write!(f, "no edge from node {} to node {}", from.0, to.0)
+            }
+            PathError::WrongDistance { reported, actual } => write!(
+                f,
+                "reported distance {} but path edges sum to {}",
+                reported, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
 /// Result of shortest path computation
 #[derive(Debug, Clone)]
 pub struct ShortestPathResult {
@@ -143,15 +904,37 @@ pub struct ShortestPathResult {
     pub target: NodeId,
     pub distance: Option<u32>,
     pub path: Vec<NodeId>,
+    /// Nearest source for multi-source searches; `None` for single-source
+    pub reached_from: Option<NodeId>,
+    /// Nodes the search finalized before stopping, as a measure of work
+    pub nodes_expanded: usize,
+    /// Some relaxation's path cost exceeded `u32::MAX` and was dropped, so an
+    /// unreachable or too-long result may really be an overflow. Only
+    /// `dijkstra_overflow_checked` detects this; every other search
+    /// saturates silently and leaves it `false`.
+    pub overflowed: bool,
 }
 
 impl ShortestPathResult {
+    /// Result for a query whose target was not reached, or whose search
+    /// failed before producing one: no distance, an empty path, and no work
+    /// recorded
+    pub fn unreachable(source: NodeId, target: NodeId) -> Self {
+        Self {
+            source,
+            target,
+            distance: None,
+            path: Vec::new(),
+            reached_from: None,
+            nodes_expanded: 0,
+            overflowed: false,
+        }
+    }
+
+    /// Result for `target` from a finished search. A target the search never
+    /// reached, or one outside the graph, has distance `None` and an empty path.
     pub fn reconstruct_path(state: &DijkstraState, source: NodeId, target: NodeId) -> Self {
-        let distance = if state.distances[target.0] == u32::MAX {
-            None
-        } else {
-            Some(state.distances[target.0])
-        };
+        let distance = Self::distance_only(state, target);
 
         let mut path = Vec::new();
         if distance.is_some() {
@@ -173,6 +956,505 @@ impl ShortestPathResult {
             target,
             distance,
             path,
+            reached_from: None,
+            nodes_expanded: state.nodes_expanded,
+            overflowed: false,
+        }
+    }
+
+    /// Distance to `target` from a finished search, without walking the
+    /// predecessors or allocating a path. `None` if the search never reached
+    /// `target` or it is outside the graph.
+    pub fn distance_only(state: &DijkstraState, target: NodeId) -> Option<u32> {
+        match state.distances.get(target.0) {
+            Some(&distance) if distance != u32::MAX => Some(distance),
+            _ => None,
+        }
+    }
+
+    /// Consecutive `(from, to)` node pairs along `path`, in order. Empty
+    /// for an empty or single-node path.
+    pub fn segments(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.path.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    /// The edges along `path` as `(from, to, weight)`, looked up in `graph`.
+    /// With parallel edges, the lightest one between two nodes is used.
+    /// Empty for an empty or single-node path.
+    pub fn path_edges(&self, graph: &Graph) -> Result<Vec<(NodeId, NodeId, u32)>, PathError> {
+        self.segments()
+            .map(|(from, to)| {
+                graph
+                    .adjacency_list
+                    .get(from.0)
+                    .into_iter()
+                    .flatten()
+                    .filter(|edge| edge.to == to)
+                    .map(|edge| edge.weight)
+                    .min()
+                    .map(|weight| (from, to, weight))
+                    .ok_or(PathError::MissingEdge { from, to })
+            })
+            .collect()
+    }
+
+    /// Check that `path` is a real walk from `source` to `target` in `graph`
+    /// whose edge weights sum to `distance`. With parallel edges, the lightest
+    /// one between two nodes is used.
+    pub fn verify(&self, graph: &Graph) -> Result<(), PathError> {
+        let reported = match (self.distance, self.path.is_empty()) {
+            (None, true) => return Ok(()),
+            (Some(distance), false) => distance,
+            (distance, _) => {
+                return Err(PathError::PathDistanceMismatch {
+                    distance,
+                    path_len: self.path.len(),
+                })
+            }
+        };
+
+        let first = self.path[0];
+        let last = self.path[self.path.len() - 1];
+        if first != self.source || last != self.target {
+            return Err(PathError::WrongEndpoints { first, last });
+        }
+
+        let actual = self
+            .path_edges(graph)?
+            .iter()
+            .fold(0u32, |sum, &(_, _, weight)| sum.saturating_add(weight));
+
+        if actual != reported {
+            return Err(PathError::WrongDistance { reported, actual });
         }
+
+        Ok(())
+    }
+
+    /// Reconstruct the path to `target` from a multi-source search. The path
+    /// starts at whichever source reached `target`, which is recorded in both
+    /// `source` and `reached_from`; if `target` is unreachable, `source` is
+    /// `sources[0]`.
+    pub fn reconstruct_multi_source_path(
+        state: &DijkstraState,
+        sources: &[NodeId],
+        target: NodeId,
+    ) -> Self {
+        let distance = if state.distances[target.0] == u32::MAX {
+            None
+        } else {
+            Some(state.distances[target.0])
+        };
+
+        let mut path = Vec::new();
+        if distance.is_some() {
+            let mut current = target;
+            path.push(current);
+            while let Some(pred) = state.predecessors[current.0] {
+                current = pred;
+                path.push(current);
+            }
+            path.reverse();
+        }
+
+        let reached_from = path.first().copied();
+        Self {
+            source: reached_from.unwrap_or(sources[0]),
+            target,
+            distance,
+            path,
+            reached_from,
+            nodes_expanded: state.nodes_expanded,
+            overflowed: false,
+        }
+    }
+}
+
+/// Distances from `source` to every node; `None` marks unreachable nodes
+#[derive(Debug, Clone)]
+pub struct AllDistancesResult {
+    #[allow(dead_code, reason = "read by callers of the all-distances search, not by the cross-check")]
+    pub source: NodeId,
+    pub distances: Vec<Option<u32>>,
+    #[allow(dead_code, reason = "read by callers of the all-distances search, not by the cross-check")]
+    pub predecessors: Vec<Option<NodeId>>,
+}
+
+impl AllDistancesResult {
+    pub fn from_state(state: &DijkstraState, source: NodeId) -> Self {
+        Self {
+            source,
+            distances: state
+                .distances
+                .iter()
+                .map(|&d| if d == u32::MAX { None } else { Some(d) })
+                .collect(),
+            predecessors: state.predecessors.clone(),
+        }
+    }
+
+    #[allow(dead_code, reason = "read by callers of the all-distances search, not by the cross-check")]
+    pub fn reachable_count(&self) -> usize {
+        self.distances.iter().filter(|d| d.is_some()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tier_baselines::dijkstra_tier1_baseline;
+
+    #[test]
+    fn graph_cache_returns_the_same_arc_for_the_same_parameters() {
+        let mut cache = GraphCache::new(100);
+        assert!(cache.is_empty());
+
+        let first = cache.get_or_generate(50, 150, 1);
+        let second = cache.get_or_generate(50, 150, 1);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+
+        let other = cache.get_or_generate(50, 150, 2);
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn unit_grid_corner_to_corner_takes_rows_plus_cols_hops() {
+        let graph = Arc::new(Graph::grid(3, 3, 1..=1));
+        assert_eq!(graph.nodes, 9);
+        // 4 neighbors for the center, 3 for edges, 2 for corners
+        assert_eq!(graph.adjacency_list[4].len(), 4);
+        assert_eq!(graph.adjacency_list[1].len(), 3);
+        assert_eq!(graph.adjacency_list[0].len(), 2);
+
+        let result = dijkstra_tier1_baseline(graph, NodeId(0), NodeId(8));
+        assert_eq!(result.distance, Some(4));
+        assert_eq!(result.path.len() - 1, 4);
+    }
+
+    #[test]
+    fn grid_stats_report_a_narrow_degree_range_and_connectivity() {
+        let stats = Graph::grid(10, 10, 1..=5).stats();
+        assert_eq!(stats.nodes, 100);
+        // Each of the 180 undirected grid edges is stored in both directions
+        assert_eq!(stats.edges, 360);
+        assert_eq!((stats.min_degree, stats.max_degree), (2, 4));
+        assert!((stats.mean_degree - 3.6).abs() < 1e-9, "{}", stats.mean_degree);
+        assert!(stats.connected);
+
+        let mut split = Graph::new(3);
+        split.add_edge(NodeId(0), NodeId(1), 1);
+        split.add_edge(NodeId(1), NodeId(0), 1);
+        assert!(!split.stats().connected);
+    }
+
+    #[test]
+    fn barabasi_albert_is_connected_with_hubs() {
+        let graph = Graph::barabasi_albert(1000, 2, 7);
+        let stats = graph.stats();
+
+        assert!(stats.connected);
+        assert!(stats.min_degree >= 2);
+        assert!(
+            stats.max_degree as f64 > 5.0 * stats.mean_degree,
+            "max {} vs mean {:.2}",
+            stats.max_degree,
+            stats.mean_degree
+        );
+        // A uniform random graph of the same size has no such hubs
+        let uniform = Graph::random_connected_seeded(1000, stats.edges / 2, 100, 7).stats();
+        assert!(stats.max_degree > 2 * uniform.max_degree);
+    }
+
+    fn line_result(path: Vec<NodeId>, distance: Option<u32>) -> ShortestPathResult {
+        ShortestPathResult {
+            source: NodeId(0),
+            target: NodeId(2),
+            distance,
+            path,
+            reached_from: None,
+            nodes_expanded: 0,
+            overflowed: false,
+        }
+    }
+
+    #[test]
+    fn verify_rejects_wrong_distances_and_broken_paths() {
+        // 0 -2-> 1 -3-> 2
+        let mut graph = Graph::new(3);
+        graph.add_edge(NodeId(0), NodeId(1), 2);
+        graph.add_edge(NodeId(1), NodeId(2), 3);
+
+        let path = vec![NodeId(0), NodeId(1), NodeId(2)];
+        assert_eq!(line_result(path.clone(), Some(5)).verify(&graph), Ok(()));
+
+        let wrong = line_result(path, Some(4)).verify(&graph).unwrap_err();
+        assert_eq!(wrong, PathError::WrongDistance { reported: 4, actual: 5 });
+        assert!(wrong.to_string().contains('4') && wrong.to_string().contains('5'), "{}", wrong);
+
+        let skipped = line_result(vec![NodeId(0), NodeId(2)], Some(5)).verify(&graph);
+        assert_eq!(skipped, Err(PathError::MissingEdge { from: NodeId(0), to: NodeId(2) }));
+        assert_eq!(skipped.unwrap_err().to_string(), "no edge from node 0 to node 2");
+
+        let truncated = line_result(vec![NodeId(0), NodeId(1)], Some(2)).verify(&graph);
+        assert_eq!(truncated, Err(PathError::WrongEndpoints { first: NodeId(0), last: NodeId(1) }));
+
+        let empty = line_result(Vec::new(), Some(5)).verify(&graph);
+        assert_eq!(empty, Err(PathError::PathDistanceMismatch { distance: Some(5), path_len: 0 }));
+    }
+
+    #[test]
+    fn four_node_path_yields_three_segments_in_order() {
+        let path = vec![NodeId(4), NodeId(0), NodeId(7), NodeId(2)];
+        let segments: Vec<_> = line_result(path, Some(9)).segments().collect();
+        assert_eq!(
+            segments,
+            [(NodeId(4), NodeId(0)), (NodeId(0), NodeId(7)), (NodeId(7), NodeId(2))]
+        );
+
+        assert_eq!(line_result(vec![NodeId(4)], Some(0)).segments().count(), 0);
+        assert_eq!(ShortestPathResult::unreachable(NodeId(0), NodeId(1)).segments().count(), 0);
+    }
+
+    #[test]
+    fn path_edge_weights_sum_to_the_distance() {
+        let graph = Arc::new(Graph::random_connected_seeded(200, 800, 100, 9));
+        let result = dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(150));
+        let edges = result.path_edges(&graph).unwrap();
+
+        assert_eq!(edges.len(), result.path.len() - 1);
+        assert!(edges.windows(2).all(|pair| pair[0].1 == pair[1].0));
+        let total: u32 = edges.iter().map(|&(_, _, weight)| weight).sum();
+        assert_eq!(Some(total), result.distance);
+
+        let mut line = Graph::new(3);
+        line.add_edge(NodeId(0), NodeId(1), 2);
+        let skipped = line_result(vec![NodeId(0), NodeId(1), NodeId(2)], Some(5)).path_edges(&line);
+        assert_eq!(skipped, Err(PathError::MissingEdge { from: NodeId(1), to: NodeId(2) }));
+    }
+
+    #[test]
+    fn exponential_weights_are_right_skewed() {
+        let graph = Graph::random_with_weights(1_000, 10_000, WeightDistribution::Exponential { lambda: 0.1 }, 4);
+        let mut weights: Vec<u32> = graph.adjacency_list.iter().flatten().map(|edge| edge.weight).collect();
+        weights.sort_unstable();
+
+        let mean = weights.iter().map(|&w| w as f64).sum::<f64>() / weights.len() as f64;
+        let median = weights[weights.len() / 2] as f64;
+        assert!((mean - 11.0).abs() < 1.0, "mean {}", mean);
+        // A long right tail pulls the mean above the median
+        assert!(median < mean, "median {} mean {}", median, mean);
+
+        // Ten-wide buckets each hold fewer weights than the one before
+        let mut histogram = [0usize; 4];
+        for &weight in &weights {
+            if let Some(bucket) = histogram.get_mut((weight as usize - 1) / 10) {
+                *bucket += 1;
+            }
+        }
+        assert!(histogram.windows(2).all(|pair| pair[0] > pair[1]), "{:?}", histogram);
+        assert!(*weights.last().unwrap() > 50);
+    }
+
+    #[test]
+    fn dedup_keeps_the_lightest_of_parallel_edges() {
+        let mut graph = Graph::new(3);
+        graph.add_edge(NodeId(0), NodeId(1), 5);
+        graph.add_edge(NodeId(0), NodeId(2), 4);
+        graph.add_edge(NodeId(0), NodeId(1), 3);
+        graph.add_edge(NodeId(1), NodeId(2), 1);
+        let before = dijkstra_tier1_baseline(Arc::new(graph.clone()), NodeId(0), NodeId(2));
+
+        graph.dedup_edges();
+        let neighbors: Vec<(NodeId, u32)> = graph.adjacency_list[0].iter().map(|edge| (edge.to, edge.weight)).collect();
+        assert_eq!(neighbors, [(NodeId(1), 3), (NodeId(2), 4)]);
+        assert_eq!(graph.adjacency_list[1].len(), 1);
+
+        let after = dijkstra_tier1_baseline(Arc::new(graph), NodeId(0), NodeId(2));
+        assert_eq!(after.distance, before.distance);
+        assert_eq!(after.distance, Some(4));
+    }
+
+    #[test]
+    fn two_components_get_two_labels() {
+        // {0, 2, 4} and {1, 3}, joined only by directed edges
+        let mut graph = Graph::new(5);
+        graph.add_edge(NodeId(4), NodeId(2), 1);
+        graph.add_edge(NodeId(2), NodeId(0), 1);
+        graph.add_edge(NodeId(3), NodeId(1), 1);
+
+        assert_eq!(graph.connected_components(), [0, 1, 0, 1, 0]);
+        assert!(!graph.is_connected());
+
+        graph.add_edge(NodeId(1), NodeId(4), 1);
+        assert_eq!(graph.connected_components(), [0; 5]);
+        assert!(graph.is_connected());
+        assert!(Graph::new(0).is_connected());
+        assert!(Graph::random_connected_seeded(200, 600, 100, 2).is_connected());
+    }
+
+    const DIMACS: &str = "\
+c 4-node example
+p sp 4 5
+a 1 2 7
+a 2 3 1
+a 1 3 10
+c arcs are directed
+a 3 4 2
+a 4 1 5
+";
+
+    fn dimacs_error(text: &str) -> ParseError {
+        Graph::from_dimacs(text.as_bytes()).unwrap_err()
+    }
+
+    #[test]
+    fn dimacs_arcs_are_directed_and_zero_indexed() {
+        let graph = Graph::from_dimacs(DIMACS.as_bytes()).unwrap();
+        assert_eq!(graph.nodes, 4);
+        assert_eq!(graph.stats().edges, 5);
+
+        let edge = &graph.adjacency_list[0][1];
+        assert_eq!((edge.to, edge.weight), (NodeId(2), 10));
+        // 1 -> 2 does not imply 2 -> 1
+        assert!(graph.adjacency_list[1].iter().all(|e| e.to != NodeId(0)));
+    }
+
+    #[test]
+    fn malformed_dimacs_lines_report_their_line_number() {
+        let cases = [
+            ("a 1 2 3\n", 1, "arc before problem line"),
+            ("p sp 2 1\np sp 2 1\n", 2, "duplicate problem line"),
+            ("p sp 2 1\na 1 3 4\n", 2, "node 3 out of range 1..=2"),
+            ("p sp 2 1\na 0 1 4\n", 2, "node 0 out of range 1..=2"),
+            ("p sp 2 1\na 1 2 heavy\n", 2, "invalid weight 'heavy'"),
+            ("p sp two 1\n", 1, "invalid node count 'two'"),
+            ("p sp 2 1\nx 1 2\n", 2, "unrecognized line 'x 1 2'"),
+        ];
+        for (text, line, message) in cases {
+            let err = dimacs_error(text);
+            assert_eq!((err.line, err.message.as_str()), (line, message), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn dimacs_file_errors_have_no_line_number() {
+        let err = dimacs_error("c only comments\n");
+        assert_eq!((err.line, err.to_string().as_str()), (0, "missing problem line"));
+
+        let err = dimacs_error("p sp 2 2\na 1 2 4\n");
+        assert_eq!(err.line, 0);
+        assert_eq!(err.message, "problem line declares 2 arcs but 1 were given");
+        assert_eq!(dimacs_error("p sp 2 1\na 1 2 x\n").to_string(), "line 2: invalid weight 'x'");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json_round_trip_is_byte_identical() {
+        let graph = Graph::random_connected_seeded(50, 200, 100, 8);
+        let json = graph.to_json();
+        let restored = Graph::from_json(&json).unwrap();
+
+        assert_eq!(restored.to_json(), json);
+        let order = |g: &Graph| -> Vec<Vec<(NodeId, u32)>> {
+            g.adjacency_list.iter().map(|edges| edges.iter().map(|e| (e.to, e.weight)).collect()).collect()
+        };
+        assert_eq!(order(&restored), order(&graph));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json_node_ids_are_plain_integers() {
+        let mut graph = Graph::new(2);
+        graph.add_edge(NodeId(0), NodeId(1), 4);
+        assert_eq!(graph.to_json(), r#"{"nodes":2,"adjacency_list":[[{"to":1,"weight":4}],[]]}"#);
+
+        let err = Graph::from_json(r#"{"nodes":2,"adjacency_list":[[{"to":2,"weight":4}],[]]}"#).unwrap_err();
+        assert_eq!(err.message, "edge target 2 out of range for 2 nodes");
+        assert!(Graph::from_json(r#"{"nodes":3,"adjacency_list":[]}"#).is_err());
+    }
+
+    #[test]
+    fn adjacency_matrix_edges_match_hand_computed_distances() {
+        const NO: u32 = u32::MAX;
+        // 0 -4-> 1, 0 -9-> 2, 1 -2-> 2, 2 -1-> 0
+        let matrix = vec![vec![NO, 4, 9], vec![NO, NO, 2], vec![1, NO, NO]];
+        let graph = Arc::new(Graph::from_adjacency_matrix(&matrix, NO).unwrap());
+
+        assert_eq!(graph.nodes, 3);
+        assert_eq!(graph.stats().edges, 4);
+        let distance = |from, to| dijkstra_tier1_baseline(graph.clone(), NodeId(from), NodeId(to)).distance;
+        assert_eq!(distance(0, 2), Some(6));
+        assert_eq!(distance(1, 0), Some(3));
+        assert_eq!(distance(2, 1), Some(5));
+
+        let ragged = vec![vec![NO, 4, 9], vec![NO, 2], vec![1, NO, NO]];
+        let err = Graph::from_adjacency_matrix(&ragged, NO).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: row has 2 columns, expected 3");
+    }
+
+    #[test]
+    fn average_degree_four_gives_about_two_thousand_edges() {
+        let stats = Graph::random_with_avg_degree(1000, 4.0, 100, 3).stats();
+        // Adjacency entries count each undirected edge twice
+        let undirected = stats.edges / 2;
+        assert!((1900..=2000).contains(&undirected), "{}", undirected);
+        assert!((stats.mean_degree - 4.0).abs() < 0.2, "{}", stats.mean_degree);
+        assert!(stats.connected);
+    }
+
+    #[test]
+    fn forward_path_order_compares_from_the_source() {
+        // Tree 0 -> 1 -> 2, 0 -> 4 -> 3, and a second root 5 -> 6
+        let mut predecessors = vec![None; 8];
+        for (node, pred) in [(1, 0), (2, 1), (4, 0), (3, 4), (6, 5)] {
+            predecessors[node] = Some(NodeId(pred));
+        }
+        let order = |a, b| forward_path_order(&predecessors, NodeId(a), NodeId(b), NodeId(7));
+
+        // 0,1,2,7 < 0,4,3,7 although 2 > 3
+        assert_eq!(order(2, 3), Ordering::Less);
+        // One path runs through the other's endpoint: 0,1,7 vs 0,1,2,7
+        assert_eq!(order(1, 2), Ordering::Greater);
+        assert_eq!(order(2, 1), Ordering::Less);
+        // Different roots decide at once
+        assert_eq!(order(6, 3), Ordering::Greater);
+        assert_eq!(order(2, 2), Ordering::Equal);
+    }
+
+    #[test]
+    fn updating_a_weight_on_the_shortest_path_changes_the_next_query() {
+        // 0 -> 1 -> 3 costs 2; 0 -> 2 -> 3 costs 5
+        let mut graph = Graph::new(4);
+        graph.add_bidirectional_edge(NodeId(0), NodeId(1), 1);
+        graph.add_bidirectional_edge(NodeId(1), NodeId(3), 1);
+        graph.add_bidirectional_edge(NodeId(0), NodeId(2), 2);
+        graph.add_bidirectional_edge(NodeId(2), NodeId(3), 3);
+        let query = |graph: &Graph| dijkstra_tier1_baseline(Arc::new(graph.clone()), NodeId(0), NodeId(3));
+        assert_eq!(query(&graph).distance, Some(2));
+
+        assert_eq!(graph.update_edge_weight(NodeId(1), NodeId(3), 10), Ok(1));
+        let rerouted = query(&graph);
+        assert_eq!(rerouted.distance, Some(5));
+        assert_eq!(rerouted.path, [NodeId(0), NodeId(2), NodeId(3)]);
+        // The reverse edge followed
+        assert_eq!(graph.update_edge_weight(NodeId(3), NodeId(1), 10), Ok(10));
+    }
+
+    #[test]
+    fn updating_a_missing_edge_is_an_error() {
+        let mut graph = Graph::new(3);
+        graph.add_edge(NodeId(0), NodeId(1), 4);
+
+        assert_eq!(
+            graph.update_edge_weight(NodeId(1), NodeId(0), 2),
+            Err(GraphError::EdgeNotFound { from: NodeId(1), to: NodeId(0) })
+        );
+        assert_eq!(
+            graph.update_edge_weight(NodeId(0), NodeId(3), 2),
+            Err(GraphError::NodeOutOfRange { node: NodeId(3), nodes: 3 })
+        );
+        assert_eq!(graph.adjacency_list[0][0].weight, 4);
     }
 }
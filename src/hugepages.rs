@@ -0,0 +1,125 @@
+use crate::graph::{DijkstraState, Graph, NodeId, QueueNode, ShortestPathResult};
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+
+// ============================================================================
+// Transparent Huge Page Hints
+// ============================================================================
+//
+// With the `hugepages` feature on Linux, large DijkstraState vectors are
+// advised with MADV_HUGEPAGE so the kernel may back them with huge pages,
+// reducing TLB misses on big graphs. This is best-effort: the kernel may
+// ignore the hint, and on other platforms or without the feature every
+// function here is a no-op wrapper around the regular allocation.
+
+/// Hint that the pages fully covered by `buf` should use transparent huge
+/// pages. Only the page-aligned interior of the buffer is advised.
+pub fn advise_hugepages<T>(buf: &[T]) {
+    #[cfg(all(feature = "hugepages", target_os = "linux"))]
+    {
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if page <= 0 {
+            return;
+        }
+        let page = page as usize;
+        let start = buf.as_ptr() as usize;
+        let end = start + std::mem::size_of_val(buf);
+        let aligned_start = (start + page - 1) & !(page - 1);
+        let aligned_end = end & !(page - 1);
+        if aligned_end > aligned_start {
+            unsafe {
+                libc::madvise(
+                    aligned_start as *mut libc::c_void,
+                    aligned_end - aligned_start,
+                    libc::MADV_HUGEPAGE,
+                );
+            }
+        }
+    }
+
+    #[cfg(not(all(feature = "hugepages", target_os = "linux")))]
+    let _ = buf;
+}
+
+impl DijkstraState {
+    /// Same as `DijkstraState::new`, but with huge page hints applied to
+    /// the state vectors
+    pub fn new_hugepage_hinted(nodes: usize, source: NodeId) -> Self {
+        let state = Self::new(nodes, source);
+        advise_hugepages(&state.distances);
+        advise_hugepages(&state.predecessors);
+        advise_hugepages(&state.visited);
+        state
+    }
+}
+
+/// Tier 1 baseline using a huge-page-hinted state; results are identical
+/// to `dijkstra_tier1_baseline`
+pub fn dijkstra_hugepage_baseline(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let mut state = DijkstraState::new_hugepage_hinted(graph.nodes, source);
+
+    let mut queue = BinaryHeap::new();
+    queue.push(QueueNode {
+        node: source,
+        distance: 0,
+    });
+
+    while let Some(QueueNode { node, distance }) = queue.pop() {
+        if state.visited[node.0] || distance > state.distances[node.0] {
+            continue;
+        }
+
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        for edge in &graph.adjacency_list[node.0] {
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+
+                queue.push(QueueNode {
+                    node: edge.to,
+                    distance: new_distance,
+                });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    ShortestPathResult::reconstruct_path(&state, source, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tier_baselines::dijkstra_tier1_baseline;
+
+    #[test]
+    #[cfg_attr(not(all(feature = "hugepages", target_os = "linux")), ignore)]
+    fn hinted_state_gives_the_baseline_result() {
+        // Large enough that the state vectors span several pages
+        let graph = Arc::new(Graph::random_connected_seeded(20_000, 60_000, 100, 2));
+        let (source, target) = (NodeId(0), NodeId(19_999));
+
+        let state = DijkstraState::new_hugepage_hinted(graph.nodes, source);
+        assert_eq!(state.distances[source.0], 0);
+        assert!(state.distances[1..].iter().all(|&d| d == u32::MAX));
+
+        let expected = dijkstra_tier1_baseline(graph.clone(), source, target);
+        let hinted = dijkstra_hugepage_baseline(graph, source, target);
+        assert_eq!(hinted.distance, expected.distance);
+        assert_eq!(hinted.path, expected.path);
+        assert_eq!(hinted.nodes_expanded, expected.nodes_expanded);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::graph::{Graph, NodeId, ShortestPathResult};
+use crate::tier_baselines::dijkstra_tier1_baseline;
+use hashbrown::HashSet;
+use std::sync::Arc;
+
+/// Top-`k` loopless shortest paths from `source` to `target` using Yen's
+/// algorithm, with `dijkstra_tier1_baseline` computing each spur path.
+/// Results are sorted ascending by distance and contain no duplicate paths;
+/// fewer than `k` are returned if the graph has fewer distinct paths.
+pub fn k_shortest_paths(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    k: usize,
+) -> Vec<ShortestPathResult> {
+    let mut accepted: Vec<ShortestPathResult> = Vec::new();
+    if k == 0 {
+        return accepted;
+    }
+
+    let first = dijkstra_tier1_baseline(graph.clone(), source, target);
+    if first.distance.is_none() {
+        return accepted;
+    }
+    accepted.push(first);
+
+    let mut candidates: Vec<ShortestPathResult> = Vec::new();
+
+    while accepted.len() < k {
+        let previous = accepted.last().unwrap().path.clone();
+
+        for spur_index in 0..previous.len() - 1 {
+            let spur_node = previous[spur_index];
+            let root = &previous[..=spur_index];
+
+            // Forbid the next edge of every accepted path sharing this root,
+            // and every root node except the spur node to keep paths loopless
+            let mut removed_edges = HashSet::new();
+            for path in accepted.iter().map(|r| &r.path) {
+                if path.len() > spur_index + 1 && &path[..=spur_index] == root {
+                    removed_edges.insert((path[spur_index].0, path[spur_index + 1].0));
+                }
+            }
+            let removed_nodes: HashSet<usize> =
+                root[..spur_index].iter().map(|node| node.0).collect();
+
+            let view = Arc::new(filtered_graph(&graph, &removed_edges, &removed_nodes));
+            let spur = dijkstra_tier1_baseline(view, spur_node, target);
+            if spur.distance.is_none() {
+                continue;
+            }
+
+            let mut path = root[..spur_index].to_vec();
+            path.extend_from_slice(&spur.path);
+
+            let duplicate = accepted
+                .iter()
+                .chain(candidates.iter())
+                .any(|r| r.path == path);
+            if !duplicate {
+                candidates.push(ShortestPathResult {
+                    source,
+                    target,
+                    distance: path_cost(&graph, &path),
+                    path,
+                    reached_from: None,
+                    nodes_expanded: spur.nodes_expanded,
+                    overflowed: false,
+                });
+            }
+        }
+
+        if candidates.is_empty() {
+            break;
+        }
+
+        // Ties broken by path so results are deterministic
+        let best = candidates
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.distance.cmp(&b.distance).then_with(|| {
+                    let a_ids = a.path.iter().map(|node| node.0);
+                    let b_ids = b.path.iter().map(|node| node.0);
+                    a_ids.cmp(b_ids)
+                })
+            })
+            .map(|(index, _)| index)
+            .unwrap();
+        accepted.push(candidates.swap_remove(best));
+    }
+
+    accepted
+}
+
+/// Copy of `graph` without the given directed edges and without any edges
+/// touching the given nodes
+fn filtered_graph(
+    graph: &Graph,
+    removed_edges: &HashSet<(usize, usize)>,
+    removed_nodes: &HashSet<usize>,
+) -> Graph {
+    let mut view = Graph::new(graph.nodes);
+    for (from, edges) in graph.adjacency_list.iter().enumerate() {
+        if removed_nodes.contains(&from) {
+            continue;
+        }
+        for edge in edges {
+            if !removed_nodes.contains(&edge.to.0) && !removed_edges.contains(&(from, edge.to.0)) {
+                view.add_edge(NodeId(from), edge.to, edge.weight);
+            }
+        }
+    }
+    view
+}
+
+/// Total weight of `path`, taking the lightest edge between consecutive nodes
+fn path_cost(graph: &Graph, path: &[NodeId]) -> Option<u32> {
+    path.windows(2).try_fold(0u32, |total, pair| {
+        let weight = graph.adjacency_list[pair[0].0]
+            .iter()
+            .filter(|edge| edge.to == pair[1])
+            .map(|edge| edge.weight)
+            .min()?;
+        Some(total.saturating_add(weight))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diamond_yields_both_equal_length_paths() {
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3, both of length 2, plus a direct
+        // 0 -> 3 edge of length 5
+        let mut graph = Graph::new(4);
+        graph.add_edge(NodeId(0), NodeId(1), 1);
+        graph.add_edge(NodeId(1), NodeId(3), 1);
+        graph.add_edge(NodeId(0), NodeId(2), 1);
+        graph.add_edge(NodeId(2), NodeId(3), 1);
+        graph.add_edge(NodeId(0), NodeId(3), 5);
+        let graph = Arc::new(graph);
+
+        let paths = k_shortest_paths(graph.clone(), NodeId(0), NodeId(3), 2);
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().all(|p| p.distance == Some(2)));
+        assert_ne!(paths[0].path, paths[1].path);
+
+        let all = k_shortest_paths(graph, NodeId(0), NodeId(3), 10);
+        let distances: Vec<_> = all.iter().map(|p| p.distance).collect();
+        assert_eq!(distances, vec![Some(2), Some(2), Some(5)]);
+    }
+
+    #[test]
+    fn unreachable_target_has_no_paths() {
+        let graph = Arc::new(Graph::new(2));
+        assert!(k_shortest_paths(graph, NodeId(0), NodeId(1), 3).is_empty());
+    }
+}
@@ -1,53 +1,113 @@
+#[cfg(feature = "tokio")]
+#[allow(dead_code, reason = "opt-in async chain API; the driver benchmarks the synchronous chain")]
+mod async_chain;
+mod baseline;
+mod bench_config;
+#[allow(dead_code, reason = "the original runner, superseded by comprehensive_benchmarking")]
 mod benchmarking;
+mod cli;
 mod comprehensive_benchmarking;
+mod criterion_export;
+mod cross_check;
 mod dijkstra_eventchains;
+mod dijkstra_traditional;
 mod dijkstra_events;
 mod eventchains;
+mod fixed_state;
 mod graph;
+mod hugepages;
+mod k_shortest;
+mod labeled_graph;
 mod middleware;
 mod noop_middleware;
+mod priority_queues;
 mod tier_baselines;
+mod verdict;
+mod visitor;
+mod workload;
 
-use benchmarking::*;
 use comprehensive_benchmarking::*;
 use colored::*;
 use dijkstra_eventchains::*;
-use graph::{Graph, NodeId};
+use priority_queues::{dijkstra_btreeset_baseline, dijkstra_indexed_heap_baseline};
+use dijkstra_events::EventContextBuilder;
+use eventchains::{ChainStatus, DispatchStrategy, EventMiddleware, FaultToleranceMode};
+use fixed_state::dijkstra_fixed_baseline;
+use graph::{CsrGraph, DijkstraState, Graph, GraphCache, NodeId, ShortestPathResult, DEFAULT_SEED};
+use middleware::{
+    CircuitBreakerMiddleware, MemoizingMiddleware, MemoryBudgetMiddleware, MemoryProfilingMiddleware, Observer,
+    ParallelObserverMiddleware, PathCache, ReplayMiddleware, TimeoutMiddleware,
+};
+use noop_middleware::{CountingMiddleware, NoOpObserver};
 use tier_baselines::*;
 
+use baseline::{
+    compare_allocations, compare_to_baseline, print_allocation_comparison, print_baseline_comparison,
+    BaselineEntry, BaselineFile, PEAK_MEMORY_TOLERANCE_PCT,
+};
+use bench_config::{BenchConfig, DEFAULT_BENCH_RUNS};
+use cli::{no_color_requested, CliOptions, USAGE};
+use criterion_export::write_criterion_benchmark;
+use verdict::{print_verdict_summary, TierVerdict};
+use workload::{average_hops, generate_query_workload, QueryBias};
+
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 // Use the tracking allocator for memory profiling
 #[global_allocator]
 static GLOBAL: comprehensive_benchmarking::TrackingAllocator = comprehensive_benchmarking::TrackingAllocator;
 
+// Progress lines are suppressed in parallel mode, where concurrent test
+// cases would interleave them
+static SHOW_PROGRESS: AtomicBool = AtomicBool::new(true);
+
+fn progress_section(title: &str) {
+    if SHOW_PROGRESS.load(Ordering::Relaxed) {
+        println!("\n{}", title.bright_yellow().bold());
+    }
+}
+
+fn progress_start(label: &str) {
+    if SHOW_PROGRESS.load(Ordering::Relaxed) {
+        print!("{}", label);
+        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+    }
+}
+
+fn progress_done() {
+    if SHOW_PROGRESS.load(Ordering::Relaxed) {
+        println!(" ✓");
+    }
+}
+
 fn run_tier1_comprehensive(
     graph: Arc<Graph>,
     source: NodeId,
     target: NodeId,
     runs: usize,
+    options: &BenchmarkOptions,
 ) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
-    println!("\n{}", "Running Tier 1 Comprehensive Benchmarks...".bright_yellow().bold());
+    progress_section("Running Tier 1 Comprehensive Benchmarks...");
 
     // Baseline: Bare function calls
-    print!("  Benchmarking bare function calls...");
-    std::io::Write::flush(&mut std::io::stdout()).unwrap();
-    let bare_functions = run_comprehensive_benchmark(runs, || {
+    progress_start("  Benchmarking bare function calls...");
+    let bare_functions = run_comprehensive_benchmark_with(runs, options, || {
         let g = graph.clone();
-        let result = dijkstra_tier1_baseline(g, source, target);
-        result.distance.is_some()
+        path_found(dijkstra_tier1_baseline(g, source, target))
     });
-    println!(" ✓");
+    progress_done();
 
     // EventChains: No middleware
-    print!("  Benchmarking EventChains (no middleware)...");
-    std::io::Write::flush(&mut std::io::stdout()).unwrap();
-    let eventchains_no_middleware = run_comprehensive_benchmark(runs, || {
+    progress_start("  Benchmarking EventChains (no middleware)...");
+    let eventchains_no_middleware = run_comprehensive_benchmark_with(runs, options, || {
         let g = graph.clone();
-        let result = dijkstra_eventchains_optimized(g, source, target);
-        result.distance.is_some()
+        path_found(dijkstra_eventchains_optimized(g, source, target))
     });
-    println!(" ✓");
+    progress_done();
 
     (bare_functions, eventchains_no_middleware)
 }
@@ -57,28 +117,25 @@ fn run_tier2_comprehensive(
     source: NodeId,
     target: NodeId,
     runs: usize,
+    options: &BenchmarkOptions,
 ) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
-    println!("\n{}", "Running Tier 2 Comprehensive Benchmarks...".bright_yellow().bold());
+    progress_section("Running Tier 2 Comprehensive Benchmarks...");
 
     // Baseline: Manual instrumented
-    print!("  Benchmarking manual instrumented...");
-    std::io::Write::flush(&mut std::io::stdout()).unwrap();
-    let manual_instrumented = run_comprehensive_benchmark(runs, || {
+    progress_start("  Benchmarking manual instrumented...");
+    let manual_instrumented = run_comprehensive_benchmark_with(runs, options, || {
         let g = graph.clone();
-        let result = dijkstra_tier2_baseline(g, source, target);
-        result.is_ok()
+        dijkstra_tier2_baseline(g, source, target).and_then(path_found)
     });
-    println!(" ✓");
+    progress_done();
 
     // EventChains: No middleware
-    print!("  Benchmarking EventChains (no middleware)...");
-    std::io::Write::flush(&mut std::io::stdout()).unwrap();
-    let eventchains_no_middleware = run_comprehensive_benchmark(runs, || {
+    progress_start("  Benchmarking EventChains (no middleware)...");
+    let eventchains_no_middleware = run_comprehensive_benchmark_with(runs, options, || {
         let g = graph.clone();
-        let result = dijkstra_eventchains_optimized(g, source, target);
-        result.distance.is_some()
+        path_found(dijkstra_eventchains_optimized(g, source, target))
     });
-    println!(" ✓");
+    progress_done();
 
     (manual_instrumented, eventchains_no_middleware)
 }
@@ -88,187 +145,1578 @@ fn run_tier3_comprehensive(
     source: NodeId,
     target: NodeId,
     runs: usize,
+    options: &BenchmarkOptions,
 ) -> Vec<(usize, ComprehensiveMetrics)> {
-    println!("\n{}", "Running Tier 3 Comprehensive Benchmarks...".bright_yellow().bold());
+    progress_section("Running Tier 3 Comprehensive Benchmarks...");
 
     let middleware_counts = vec![0, 1, 3, 5, 10];
     let mut results = Vec::new();
 
     for &count in &middleware_counts {
-        print!("  Benchmarking {} middleware...", count);
-        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+        progress_start(&format!("  Benchmarking {} middleware...", count));
 
-        let metrics = run_comprehensive_benchmark(runs, || {
+        let metrics = run_comprehensive_benchmark_with(runs, options, || {
             let g = graph.clone();
-            let result = dijkstra_eventchains_with_n_middleware(g, source, target, count);
-            result.distance.is_some()
+            path_found(dijkstra_eventchains_with_n_middleware(g, source, target, count))
         });
 
-        println!(" ✓");
+        progress_done();
         results.push((count, metrics));
     }
 
     results
 }
 
+/// No-op middleware counts compared under each dispatch strategy
+const DISPATCH_MIDDLEWARE_COUNTS: [usize; 4] = [1, 3, 5, 10];
+
+/// Tier 3's chain under recursive and iterative middleware dispatch, as
+/// (middleware count, recursive, iterative)
+fn run_dispatch_comparison(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> Vec<(usize, ComprehensiveMetrics, ComprehensiveMetrics)> {
+    progress_section("Running Tier 5 Dispatch Strategy Benchmarks...");
+
+    DISPATCH_MIDDLEWARE_COUNTS
+        .iter()
+        .map(|&count| {
+            let [recursive, iterative] = [
+                ("recursive", DispatchStrategy::Recursive),
+                ("iterative", DispatchStrategy::Iterative),
+            ]
+            .map(|(label, dispatch)| {
+                progress_start(&format!("  Benchmarking {} middleware, {} dispatch...", count, label));
+                let metrics = run_comprehensive_benchmark_with(runs, options, || {
+                    let g = graph.clone();
+                    path_found(dijkstra_eventchains_with_dispatch(g, source, target, count, dispatch))
+                });
+                progress_done();
+                metrics
+            });
+            (count, recursive, iterative)
+        })
+        .collect()
+}
+
+/// Square unit-weight grid with about `nodes` cells
+fn unit_grid(nodes: usize) -> Graph {
+    let side = ((nodes as f64).sqrt().round() as usize).max(2);
+    Graph::grid(side, side, 1..=1)
+}
+
+/// BFS, Tier 1 Dijkstra, and optimized EventChains corner to corner on a
+/// unit-weight grid, where BFS gives the same answer with the least work
+fn run_unit_grid_comparison(
+    nodes: usize,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> [(&'static str, ComprehensiveMetrics); 3] {
+    progress_section("Running Tier 6 Unit-Weight Grid Benchmarks...");
+
+    let graph = Arc::new(unit_grid(nodes));
+    let source = NodeId(0);
+    let target = NodeId(graph.nodes - 1);
+
+    let bfs = bfs_shortest_path_baseline(graph.clone(), source, target);
+    let dijkstra = dijkstra_tier1_baseline(graph.clone(), source, target);
+    if bfs.distance != dijkstra.distance {
+        eprintln!(
+            "{} BFS found distance {:?} on the unit grid, Dijkstra {:?}",
+            "Warning:".yellow().bold(),
+            bfs.distance,
+            dijkstra.distance
+        );
+    }
+
+    let variants: [(&'static str, DijkstraFn); 3] = [
+        ("BFS (hop count)", bfs_shortest_path_baseline),
+        ("Dijkstra (bare function calls)", dijkstra_tier1_baseline),
+        ("Dijkstra (EventChains)", dijkstra_eventchains_optimized),
+    ];
+
+    variants.map(|(label, search)| {
+        progress_start(&format!("  Benchmarking {}...", label));
+        let metrics = run_comprehensive_benchmark_with(runs, options, || {
+            let g = graph.clone();
+            path_found(search(g, source, target))
+        });
+        progress_done();
+        (label, metrics)
+    })
+}
+
+/// Edges each new node attaches with in a Barabási–Albert graph with about
+/// `edges` edges over `nodes` nodes
+fn scale_free_attachments(nodes: usize, edges: usize) -> usize {
+    (edges / nodes.max(1)).max(1)
+}
+
+/// Tier 1 baseline and optimized EventChains on a scale-free graph the size
+/// of the test case, whose hubs push many more entries through the queue
+/// than a uniform random graph's nodes do
+fn run_scale_free_comparison(
+    case: &BenchConfig,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
+    progress_section("Running Scale-Free Graph Benchmarks...");
+
+    let m = scale_free_attachments(case.nodes, case.edges);
+    let graph = Arc::new(Graph::barabasi_albert(case.nodes, m, case.seed));
+    let source = NodeId(0);
+    let target = NodeId(case.nodes - 1);
+
+    progress_start("  Benchmarking Dijkstra (bare function calls)...");
+    let baseline = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(dijkstra_tier1_baseline(g, source, target))
+    });
+    progress_done();
+
+    progress_start("  Benchmarking Dijkstra (EventChains)...");
+    let eventchains = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(dijkstra_eventchains_optimized(g, source, target))
+    });
+    progress_done();
+
+    (baseline, eventchains)
+}
+
+/// Runs per middleware count when searching for the overhead knee
+const KNEE_RUNS: usize = 30;
+const KNEE_THRESHOLD_PCT: f64 = 50.0;
+const KNEE_MAX_COUNT: usize = 32;
+
+/// Add no-op middleware one layer at a time until overhead against the
+/// zero-middleware chain exceeds `threshold_pct`. Returns the first count
+/// that crosses the threshold, or `None` if `max_count` layers stay below it.
+fn find_overhead_knee(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    threshold_pct: f64,
+    max_count: usize,
+    options: &BenchmarkOptions,
+) -> Option<usize> {
+    find_knee(threshold_pct, max_count, options, |count| {
+        let g = graph.clone();
+        path_found(dijkstra_eventchains_with_n_middleware(g, source, target, count))
+    })
+}
+
+/// Benchmark `search` with 0, 1, 2, ... middleware layers and return the
+/// first count whose overhead against 0 layers exceeds `threshold_pct`
+fn find_knee<F>(threshold_pct: f64, max_count: usize, options: &BenchmarkOptions, mut search: F) -> Option<usize>
+where
+    F: FnMut(usize) -> Result<(), String>,
+{
+    let baseline = run_comprehensive_benchmark_with(KNEE_RUNS, options, || search(0));
+
+    (1..=max_count).find(|&count| {
+        let metrics = run_comprehensive_benchmark_with(KNEE_RUNS, options, || search(count));
+        metrics.overhead_vs(&baseline) > threshold_pct
+    })
+}
+
 fn run_tier4_comprehensive(
     graph: Arc<Graph>,
     source: NodeId,
     target: NodeId,
     runs: usize,
+    options: &BenchmarkOptions,
 ) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
-    println!("\n{}", "Running Tier 4 Comprehensive Benchmarks...".bright_yellow().bold());
+    progress_section("Running Tier 4 Comprehensive Benchmarks...");
 
     // Baseline: Manual with logging and timing
-    print!("  Benchmarking manual (logging + timing)...");
-    std::io::Write::flush(&mut std::io::stdout()).unwrap();
-    let manual_with_logging_timing = run_comprehensive_benchmark(runs, || {
+    progress_start("  Benchmarking manual (logging + timing)...");
+    let manual_with_logging_timing = run_comprehensive_benchmark_with(runs, options, || {
         let g = graph.clone();
         let (result, _context) = dijkstra_tier4_baseline(g, source, target, false);
-        result.distance.is_some()
+        path_found(result)
     });
-    println!(" ✓");
+    progress_done();
 
     // EventChains: With logging and timing middleware
-    print!("  Benchmarking EventChains (logging + timing)...");
-    std::io::Write::flush(&mut std::io::stdout()).unwrap();
-    let eventchains_with_logging_timing = run_comprehensive_benchmark(runs, || {
+    progress_start("  Benchmarking EventChains (logging + timing)...");
+    let eventchains_with_logging_timing = run_comprehensive_benchmark_with(runs, options, || {
         let g = graph.clone();
-        let result = dijkstra_eventchains_optimized_with_middleware(g, source, target, false);
-        result.distance.is_some()
+        path_found(dijkstra_eventchains_optimized_with_middleware(g, source, target, false))
     });
-    println!(" ✓");
+    progress_done();
 
     (manual_with_logging_timing, eventchains_with_logging_timing)
 }
 
-fn print_tier1_report(baseline: &ComprehensiveMetrics, eventchains: &ComprehensiveMetrics) {
-    print_comprehensive_comparison(
-        "TIER 1: Minimal Baseline - Cost of Orchestration Framework",
-        baseline,
-        eventchains,
+#[cfg(feature = "hugepages")]
+fn run_hugepage_comparison(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
+    progress_section("Running Huge Page Hint Benchmarks...");
+
+    progress_start("  Benchmarking regular allocation...");
+    let regular = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(dijkstra_tier1_baseline(g, source, target))
+    });
+    progress_done();
+
+    progress_start("  Benchmarking huge-page-hinted allocation...");
+    let hinted = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(hugepages::dijkstra_hugepage_baseline(g, source, target))
+    });
+    progress_done();
+
+    (regular, hinted)
+}
+
+/// Tier 1 baseline on the `Vec<Vec<Edge>>` graph vs the same graph in CSR
+/// layout, to separate memory-layout effects from framework overhead
+fn run_csr_comparison(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
+    progress_section("Running Memory Layout Benchmarks...");
+    let csr = Arc::new(CsrGraph::from(graph.as_ref()));
+
+    progress_start("  Benchmarking adjacency list layout...");
+    let adjacency = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(dijkstra_tier1_baseline(g, source, target))
+    });
+    progress_done();
+
+    progress_start("  Benchmarking CSR layout...");
+    let compressed = run_comprehensive_benchmark_with(runs, options, || {
+        let g = csr.clone();
+        path_found(dijkstra_csr_baseline(g, source, target))
+    });
+    progress_done();
+
+    (adjacency, compressed)
+}
+
+/// Tier 1 EventChains with warm caches and with caches flushed before every
+/// run, to expose the cold-start cost that infrequent queries pay
+fn run_cache_comparison(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
+    progress_section("Running Cache Policy Benchmarks...");
+
+    let [warm, cold] = [
+        ("warm", CachePolicy::Warm),
+        ("cold", CachePolicy::Cold),
+    ]
+    .map(|(label, policy)| {
+        progress_start(&format!("  Benchmarking {} caches...", label));
+        let options = options.clone().with_cache_policy(policy);
+        let metrics = run_comprehensive_benchmark_with(runs, &options, || {
+            let g = graph.clone();
+            path_found(dijkstra_eventchains_optimized(g, source, target))
+        });
+        progress_done();
+        metrics
+    });
+
+    (warm, cold)
+}
+
+/// Tier 7: the optimized chain's events run in a plain loop, boxed behind
+/// `dyn ChainableEvent` versus matched from an enum
+fn run_static_dispatch_comparison(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
+    progress_section("Running Tier 7 Static Dispatch Benchmarks...");
+
+    progress_start("  Benchmarking boxed dyn events...");
+    let dynamic = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(dijkstra_eventchains_dyn_loop(g, source, target))
+    });
+    progress_done();
+
+    progress_start("  Benchmarking enum-dispatched events...");
+    let fixed = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(dijkstra_eventchains_static(g, source, target))
+    });
+    progress_done();
+
+    (dynamic, fixed)
+}
+
+/// Tier 1 baseline and EventChains queries that return only the distance,
+/// skipping predecessor tracking (baseline) and path reconstruction
+fn run_distance_only_comparison(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
+    progress_section("Running Distance-Only Benchmarks...");
+
+    progress_start("  Benchmarking distance-only baseline...");
+    let baseline = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        distance_found(dijkstra_distance_only_baseline(g, source, target), source, target)
+    });
+    progress_done();
+
+    progress_start("  Benchmarking distance-only EventChains...");
+    let eventchains = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        distance_found(dijkstra_eventchains_distance_only(g, source, target), source, target)
+    });
+    progress_done();
+
+    (baseline, eventchains)
+}
+
+/// Context lookups per timed run in the context cost analysis; a single
+/// lookup is too short to time on its own
+const CONTEXT_LOOKUPS_PER_RUN: usize = 1000;
+
+/// Isolate the two halves of an event's context lookups: cloning the
+/// `Arc<Graph>` (a reference count bump) versus cloning the `DijkstraState`
+/// (three vectors sized to the graph)
+fn run_context_cost_analysis(
+    graph: Arc<Graph>,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> (ComprehensiveMetrics, ComprehensiveMetrics) {
+    progress_section("Running Context Lookup Cost Analysis...");
+
+    let mut context = EventContextBuilder::new().graph(graph.clone()).build();
+    context.set("state", DijkstraState::new(graph.nodes, NodeId(0)));
+
+    progress_start("  Benchmarking Arc<Graph> lookups...");
+    let arc = run_comprehensive_benchmark_with(runs, options, || {
+        for _ in 0..CONTEXT_LOOKUPS_PER_RUN {
+            std::hint::black_box(context.get::<Arc<Graph>>("graph"));
+        }
+        Ok(())
+    });
+    progress_done();
+
+    progress_start("  Benchmarking DijkstraState lookups...");
+    let state = run_comprehensive_benchmark_with(runs, options, || {
+        for _ in 0..CONTEXT_LOOKUPS_PER_RUN {
+            std::hint::black_box(context.get::<DijkstraState>("state"));
+        }
+        Ok(())
+    });
+    progress_done();
+
+    (arc, state)
+}
+
+/// Runs in the `--stream-jsonl` benchmark; far more than the test cases
+/// use, which is the point of streaming
+const STREAM_JSONL_RUNS: usize = 100_000;
+
+/// Optimized EventChains on the small test graph for `STREAM_JSONL_RUNS`
+/// runs, each written to `path` as it finishes
+fn run_streamed_benchmark(graph: Arc<Graph>, path: &Path) -> Result<StreamedMetrics, String> {
+    let source = NodeId(0);
+    let target = NodeId(graph.nodes - 1);
+
+    progress_section(&format!("Streaming {} runs to {}...", STREAM_JSONL_RUNS, path.display()));
+    progress_start("  Benchmarking EventChains (no middleware)...");
+    let metrics = run_streaming_benchmark(STREAM_JSONL_RUNS, path, &BenchmarkOptions::default(), || {
+        let g = graph.clone();
+        path_found(dijkstra_eventchains_optimized(g, source, target))
+    })?;
+    progress_done();
+
+    Ok(metrics)
+}
+
+fn print_streamed_report(metrics: &StreamedMetrics) {
+    println!("\n{}", "📼 Streamed Benchmark (online statistics)".yellow().bold());
+    println!("{}", "-".repeat(90));
+    println!("  Runs:          {}", metrics.runs);
+    println!("  Mean:          {:.2}µs", metrics.mean_duration.as_nanos() as f64 / 1000.0);
+    println!("  Std dev:       {:.2}µs", metrics.std_dev_nanos / 1000.0);
+    println!(
+        "  Min / Max:     {:.2}µs / {:.2}µs",
+        metrics.min_duration.as_nanos() as f64 / 1000.0,
+        metrics.max_duration.as_nanos() as f64 / 1000.0
+    );
+    println!("  Success rate:  {:.1}%", metrics.success_rate);
+}
+
+/// Nodes in the graph for the fixed-size state comparison, and the
+/// state's compile-time capacity
+const FIXED_STATE_NODES: usize = 16;
+const FIXED_STATE_EDGES: usize = 40;
+/// Queries on the tiny graph take well under a microsecond, so many more
+/// runs are needed for a stable CoV than in the test cases
+const FIXED_STATE_RUNS: usize = 10_000;
+
+/// Tier 1 baseline against `dijkstra_fixed_baseline` on one small graph:
+/// (vector state + heap, fixed arrays + linear scan)
+fn run_fixed_state_comparison() -> (ComprehensiveMetrics, ComprehensiveMetrics) {
+    let graph = Arc::new(Graph::random_connected(FIXED_STATE_NODES, FIXED_STATE_EDGES, 100));
+    let source = NodeId(0);
+    let target = NodeId(FIXED_STATE_NODES - 1);
+
+    progress_section("Running Fixed-Size State Benchmarks...");
+    progress_start("  Benchmarking Vec-based state...");
+    let dynamic = run_comprehensive_benchmark(FIXED_STATE_RUNS, || {
+        let g = graph.clone();
+        path_found(dijkstra_tier1_baseline(g, source, target))
+    });
+    progress_done();
+
+    progress_start("  Benchmarking fixed-size state...");
+    let fixed = run_comprehensive_benchmark(FIXED_STATE_RUNS, || {
+        let state = dijkstra_fixed_baseline::<FIXED_STATE_NODES>(&graph, source, target);
+        match state.distance(target) {
+            Some(_) => Ok(()),
+            None => Err(format!("no path from {} to {}", source.0, target.0)),
+        }
+    });
+    progress_done();
+
+    (dynamic, fixed)
+}
+
+fn print_fixed_state_report(dynamic: &ComprehensiveMetrics, fixed: &ComprehensiveMetrics) {
+    print_comprehensive_comparison(
+        &format!(
+            "Fixed-Size State - Heap-Free Dijkstra on a {}-Node Graph",
+            FIXED_STATE_NODES
+        ),
+        dynamic,
+        fixed,
+    );
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    println!(
+        "  - Latency CoV: {:.2}% with Vec state, {:.2}% with fixed arrays",
+        dynamic.coefficient_of_variation(),
+        fixed.coefficient_of_variation()
+    );
+    if fixed.coefficient_of_variation() < dynamic.coefficient_of_variation() {
+        println!("  - Avoiding the allocator made query latency more predictable");
+    } else {
+        println!("  - Allocation was not the main source of jitter at this size");
+    }
+}
+
+/// Benchmark outcome for a query whose target is known to be reachable
+fn path_found(result: ShortestPathResult) -> Result<(), String> {
+    if result.distance.is_some() {
+        Ok(())
+    } else {
+        Err(format!("no path from {} to {}", result.source.0, result.target.0))
+    }
+}
+
+/// Benchmark outcome for a distance-only query whose target is known to be
+/// reachable
+fn distance_found(distance: Option<u32>, source: NodeId, target: NodeId) -> Result<(), String> {
+    match distance {
+        Some(_) => Ok(()),
+        None => Err(format!("no path from {} to {}", source.0, target.0)),
+    }
+}
+
+type DijkstraFn = fn(Arc<Graph>, NodeId, NodeId) -> ShortestPathResult;
+
+/// Same Tier 1 Dijkstra with three priority queues on the same graph, to
+/// separate data-structure cost from framework cost
+fn run_pq_comparison(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> Vec<(&'static str, ComprehensiveMetrics)> {
+    progress_section("Running Priority Queue Benchmarks...");
+
+    let variants: [(&'static str, DijkstraFn); 3] = [
+        ("BinaryHeap (lazy deletion)", dijkstra_tier1_baseline),
+        ("BTreeSet (remove + reinsert)", dijkstra_btreeset_baseline),
+        ("Indexed heap (decrease-key)", dijkstra_indexed_heap_baseline),
+    ];
+
+    variants
+        .iter()
+        .map(|&(label, dijkstra)| {
+            progress_start(&format!("  Benchmarking {}...", label));
+            let metrics = run_comprehensive_benchmark_with(runs, options, || {
+                let g = graph.clone();
+                path_found(dijkstra(g, source, target))
+            });
+            progress_done();
+            (label, metrics)
+        })
+        .collect()
+}
+
+/// Nodes settled per event execution in the batching sweep
+const BATCH_SIZES: [usize; 4] = [1, 4, 16, 64];
+
+/// Bare EventChains Dijkstra at each of `BATCH_SIZES`, tracing the curve
+/// between one event per node and one event for the whole search
+fn run_batch_sweep(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> Vec<(usize, ComprehensiveMetrics)> {
+    progress_section("Running Batch Size Sweep...");
+
+    BATCH_SIZES
+        .iter()
+        .map(|&batch_size| {
+            progress_start(&format!("  Benchmarking batch size {}...", batch_size));
+            let metrics = run_comprehensive_benchmark_with(runs, options, || {
+                let g = graph.clone();
+                path_found(dijkstra_eventchains_batched(g, source, target, batch_size))
+            });
+            progress_done();
+            (batch_size, metrics)
+        })
+        .collect()
+}
+
+type MiddlewareFactory = fn() -> Box<dyn EventMiddleware>;
+
+/// Middleware measured one at a time around the optimized chain, each
+/// configured so that it never changes the result
+const MIDDLEWARE_CATALOGUE: [(&str, MiddlewareFactory); 6] = [
+    ("Timeout (1 s budget)", || Box::new(TimeoutMiddleware::new(Duration::from_secs(1)))),
+    ("Circuit breaker (3 failures, 1 s)", || {
+        Box::new(CircuitBreakerMiddleware::new(3, Duration::from_secs(1)))
+    }),
+    ("Memory budget (1 GiB)", || Box::new(MemoryBudgetMiddleware::new(1 << 30))),
+    ("Replay log", || Box::new(ReplayMiddleware::new())),
+    ("Counting", || Box::new(CountingMiddleware::new())),
+    ("Parallel observers (4 no-op)", || {
+        let observers = (0..4).map(|_| Box::new(NoOpObserver) as Box<dyn Observer>).collect();
+        Box::new(ParallelObserverMiddleware::new(observers))
+    }),
+];
+
+/// Optimized EventChains with each of `MIDDLEWARE_CATALOGUE` as its only
+/// middleware, then with a `MemoizingMiddleware` sharing one cache across
+/// runs, to compare against Tier 1's chain without any
+fn run_middleware_catalogue(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> Vec<(&'static str, ComprehensiveMetrics)> {
+    progress_section("Running Middleware Catalogue Benchmarks...");
+
+    let mut results: Vec<_> = MIDDLEWARE_CATALOGUE
+        .iter()
+        .map(|&(label, middleware)| {
+            progress_start(&format!("  Benchmarking {}...", label));
+            let metrics = run_comprehensive_benchmark_with(runs, options, || {
+                let g = graph.clone();
+                path_found(dijkstra_eventchains_with_middleware(g, source, target, middleware()))
+            });
+            progress_done();
+            (label, metrics)
+        })
+        .collect();
+
+    // One cache for every run, so all but the first reuse the finalized path
+    let label = "Memoizing (shared cache)";
+    let cache: PathCache = Default::default();
+    progress_start(&format!("  Benchmarking {}...", label));
+    let metrics = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        let memoizing = MemoizingMiddleware::with_cache(cache.clone());
+        path_found(dijkstra_eventchains_with_middleware(g, source, target, Box::new(memoizing)))
+    });
+    progress_done();
+    results.push((label, metrics));
+
+    // A fresh cache whose entry is invalidated before every run by
+    // re-weighting the path's first edge, alternately one heavier and back
+    let label = "Memoizing (edge updated per run)";
+    let first_hop = dijkstra_tier1_baseline(graph.clone(), source, target).segments().next();
+    if let Some((from, to)) = first_hop {
+        let memoizing = MemoizingMiddleware::new();
+        let mut graph = Arc::new(Arc::unwrap_or_clone(graph));
+        let mut weight = graph.adjacency_list[from.0]
+            .iter()
+            .find(|edge| edge.to == to)
+            .map_or(0, |edge| edge.weight);
+        progress_start(&format!("  Benchmarking {}...", label));
+        let metrics = run_comprehensive_benchmark_with(runs, options, || {
+            // The previous run's chain has dropped its clones, so this
+            // updates in place
+            let old_weight = Arc::make_mut(&mut graph)
+                .update_edge_weight(from, to, weight ^ 1)
+                .map_err(|err| err.to_string())?;
+            weight = old_weight ^ 1;
+            memoizing.invalidate_edge(from, to, old_weight, weight);
+
+            let shared = Box::new(MemoizingMiddleware::with_cache(memoizing.cache()));
+            path_found(dijkstra_eventchains_with_middleware(graph.clone(), source, target, shared))
+        });
+        progress_done();
+        results.push((label, metrics));
+    }
+
+    results
+}
+
+/// Net bytes each event of one optimized EventChains run leaves allocated,
+/// as attributed by `MemoryProfilingMiddleware`, largest first. `None` when
+/// memory tracking is disabled, since concurrent test cases would be charged
+/// for each other's allocations.
+fn profile_event_memory(graph: Arc<Graph>, source: NodeId, target: NodeId) -> Option<Vec<(String, i64)>> {
+    if !memory_tracking_enabled() {
+        return None;
+    }
+
+    let profiling = MemoryProfilingMiddleware::new();
+    let deltas = profiling.deltas();
+    dijkstra_eventchains_with_middleware(graph, source, target, Box::new(profiling));
+
+    let mut deltas: Vec<(String, i64)> = deltas
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(event, bytes)| (event.clone(), *bytes))
+        .collect();
+    deltas.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Some(deltas)
+}
+
+/// Queries answered per measured run of the workload comparison
+const WORKLOAD_QUERIES: usize = 16;
+
+/// Tier 1 baseline and EventChains answering the same batch of
+/// `WORKLOAD_QUERIES` queries for each `QueryBias`, since the framework's
+/// fixed cost per query matters less as the paths get longer. Returns each
+/// bias with the workload's mean hop count and both measurements.
+fn run_workload_comparison(
+    graph: Arc<Graph>,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> Vec<(QueryBias, f64, ComprehensiveMetrics, ComprehensiveMetrics)> {
+    progress_section("Running Query Workload Benchmarks...");
+
+    QueryBias::ALL
+        .iter()
+        .map(|&bias| {
+            let queries = generate_query_workload(&graph, WORKLOAD_QUERIES, bias);
+            let hops = average_hops(&graph, &queries);
+
+            progress_start(&format!("  Benchmarking {} workload (baseline)...", bias.label()));
+            let baseline = run_comprehensive_benchmark_with(runs, options, || {
+                for &(source, target) in &queries {
+                    std::hint::black_box(dijkstra_tier1_baseline(graph.clone(), source, target));
+                }
+                Ok(())
+            });
+            progress_done();
+
+            progress_start(&format!("  Benchmarking {} workload (EventChains)...", bias.label()));
+            let eventchains = run_comprehensive_benchmark_with(runs, options, || {
+                for &(source, target) in &queries {
+                    std::hint::black_box(dijkstra_eventchains_optimized(graph.clone(), source, target));
+                }
+                Ok(())
+            });
+            progress_done();
+
+            (bias, hops, baseline, eventchains)
+        })
+        .collect()
+}
+
+/// Fault tolerance modes compared on a failing query
+const FAULT_TOLERANCE_MODES: [FaultToleranceMode; 3] = [
+    FaultToleranceMode::Strict,
+    FaultToleranceMode::Lenient,
+    FaultToleranceMode::BestEffort,
+];
+
+/// The optimized chain under each `FaultToleranceMode`, queried from a
+/// source outside the graph so `InitializeState` fails. Strict stops there,
+/// Lenient lets every later event fail as well, and BestEffort fills in
+/// defaults so the chain finishes with an empty result. Returns each mode
+/// with the status its chain ended in.
+fn run_fault_tolerance_comparison(
+    graph: Arc<Graph>,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> Vec<(FaultToleranceMode, ChainStatus, ComprehensiveMetrics)> {
+    progress_section("Running Fault Tolerance Benchmarks...");
+
+    let source = NodeId(graph.nodes);
+    FAULT_TOLERANCE_MODES
+        .iter()
+        .map(|&mode| {
+            let (_, chain) = dijkstra_eventchains_with_fault_tolerance(graph.clone(), source, target, mode);
+
+            progress_start(&format!("  Benchmarking {:?} mode...", mode));
+            let metrics = run_comprehensive_benchmark_with(runs, options, || {
+                let g = graph.clone();
+                std::hint::black_box(dijkstra_eventchains_with_fault_tolerance(g, source, target, mode));
+                Ok(())
+            });
+            progress_done();
+
+            (mode, chain.status, metrics)
+        })
+        .collect()
+}
+
+/// Check once per test case that the baseline and EventChains results are
+/// valid paths in `graph`, so the benchmarks aren't timing a wrong answer
+fn verify_paths(graph: &Arc<Graph>, source: NodeId, target: NodeId) {
+    let results = [
+        ("Tier 1 baseline", dijkstra_tier1_baseline(graph.clone(), source, target)),
+        ("EventChains optimized", dijkstra_eventchains_optimized(graph.clone(), source, target)),
+        ("EventChains static dispatch", dijkstra_eventchains_static(graph.clone(), source, target)),
+        ("EventChains dyn loop", dijkstra_eventchains_dyn_loop(graph.clone(), source, target)),
+    ];
+
+    for (label, result) in &results {
+        match result.verify(graph) {
+            Ok(()) => println!(
+                "  {} path verified ✓ ({} nodes expanded)",
+                label, result.nodes_expanded
+            ),
+            Err(err) => eprintln!(
+                "{} {} returned an invalid path: {}",
+                "Warning:".yellow().bold(),
+                label,
+                err
+            ),
+        }
+    }
+
+    let expected = results[0].1.distance;
+    for batch_size in BATCH_SIZES {
+        let batched = dijkstra_eventchains_batched(graph.clone(), source, target, batch_size);
+        if batched.distance != expected {
+            eprintln!(
+                "{} batch size {} found distance {:?}, expected {:?}",
+                "Warning:".yellow().bold(),
+                batch_size,
+                batched.distance,
+                expected
+            );
+        }
+    }
+    println!("  Batched paths agree across batch sizes {:?} ✓", BATCH_SIZES);
+
+    let distance_only = [
+        ("Tier 1 baseline", dijkstra_distance_only_baseline(graph.clone(), source, target)),
+        ("EventChains", dijkstra_eventchains_distance_only(graph.clone(), source, target)),
+    ];
+    for (label, distance) in distance_only {
+        if distance != expected {
+            eprintln!(
+                "{} {} distance-only query found {:?}, expected {:?}",
+                "Warning:".yellow().bold(),
+                label,
+                distance,
+                expected
+            );
+        }
+    }
+    println!("  Distance-only queries agree with full paths ✓");
+
+    // A second source halfway along the id range can only shorten the path
+    let sources = [source, NodeId(graph.nodes / 2)];
+    let multi = dijkstra_multi_source_baseline(graph.clone(), &sources, target);
+    match (multi.reached_from, multi.distance) {
+        (Some(nearest), Some(distance)) => println!(
+            "  Multi-source search from {:?}: distance {} via source {}",
+            sources.map(|s| s.0),
+            distance,
+            nearest.0
+        ),
+        _ => println!(
+            "  Multi-source search from {:?}: target unreachable",
+            sources.map(|s| s.0)
+        ),
+    }
+}
+
+/// Measure only the cost of building the optimized chain (boxing four
+/// events and three middleware), without executing it
+fn bench_chain_construction(runs: usize, node_count: usize, options: &BenchmarkOptions) -> ComprehensiveMetrics {
+    progress_start("  Benchmarking chain construction...");
+    let metrics = run_comprehensive_benchmark_with(runs, options, || {
+        let chain = build_optimized_chain(NodeId(0), NodeId(node_count - 1), node_count, false);
+        std::hint::black_box(chain);
+        Ok(())
+    });
+    progress_done();
+
+    metrics
+}
+
+/// Runs per graph size when timing graph generation, which takes
+/// milliseconds at the larger sizes
+const GRAPH_CONSTRUCTION_RUNS: usize = 10;
+
+/// Time and memory-profile `Graph::random_connected` itself, the setup cost
+/// every tier pays once before its queries
+fn run_graph_construction_benchmark(
+    nodes: usize,
+    edges: usize,
+    options: &BenchmarkOptions,
+) -> ComprehensiveMetrics {
+    progress_start("  Benchmarking graph construction...");
+    let metrics = run_comprehensive_benchmark_with(GRAPH_CONSTRUCTION_RUNS, options, || {
+        let graph = Graph::random_connected(nodes, edges, 100);
+        std::hint::black_box(graph);
+        Ok(())
+    });
+    progress_done();
+
+    metrics
+}
+
+/// The Tier 1 baseline with each step's outcome wrapped in `EventResult`,
+/// isolating that part of Tier 1's overhead
+fn run_result_wrapping_benchmark(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> ComprehensiveMetrics {
+    progress_start("  Benchmarking bare function calls with EventResult wrapping...");
+    let metrics = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(dijkstra_result_wrapped_baseline(g, source, target))
+    });
+    progress_done();
+
+    metrics
+}
+
+/// Tier 2's EventChains query with `dijkstra_eventchains_optimized_checked`,
+/// which falls back to the baseline instead of reporting a failed chain as
+/// unreachable
+fn run_checked_benchmark(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    runs: usize,
+    options: &BenchmarkOptions,
+) -> ComprehensiveMetrics {
+    progress_start("  Benchmarking EventChains with baseline fallback...");
+    let metrics = run_comprehensive_benchmark_with(runs, options, || {
+        let g = graph.clone();
+        path_found(dijkstra_eventchains_optimized_checked(g, source, target))
+    });
+    progress_done();
+
+    metrics
+}
+
+fn print_tier1_report(
+    baseline: &ComprehensiveMetrics,
+    eventchains: &ComprehensiveMetrics,
+    result_wrapped: &ComprehensiveMetrics,
+) {
+    print_comprehensive_comparison(
+        "TIER 1: Minimal Baseline - Cost of Orchestration Framework",
+        baseline,
+        eventchains,
+    );
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    println!("  This tier shows the pure cost of the EventChains framework:");
+    println!("  - Event wrapping and trait dispatch overhead");
+    println!("  - Context creation and type erasure costs");
+    println!("  - Result enum wrapping impact");
+    println!("  - Memory allocations from the framework");
+
+    let wrapping_cost = result_wrapped.mean_micros() - baseline.mean_micros();
+    let framework_cost = eventchains.mean_micros() - result_wrapped.mean_micros();
+    println!("\n  Overhead breakdown (mean per query):");
+    println!("  - EventResult wrapping:            {:>9.3}μs", wrapping_cost);
+    println!("  - Events, context, and dispatch:   {:>9.3}μs", framework_cost);
+    if wrapping_cost <= 0.0 {
+        println!("  - Wrapping the step results is below the noise; the overhead is the framework's");
+    }
+}
+
+fn print_tier2_report(
+    baseline: &ComprehensiveMetrics,
+    eventchains: &ComprehensiveMetrics,
+    checked: &ComprehensiveMetrics,
+) {
+    print_comprehensive_comparison(
+        "TIER 2: Feature-Parity Baseline - Cost of Abstraction",
+        baseline,
+        eventchains,
+    );
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    println!("  This tier shows the cost of abstraction:");
+    println!("  - Generic trait-based design vs concrete types");
+    println!("  - Dynamic dispatch vs static dispatch");
+    println!("  - Type-erased context vs typed variables");
+    println!(
+        "  - Falling back to the baseline on chain failure: {:>9.3}μs mean ({:+.2}%)",
+        checked.mean_micros() - eventchains.mean_micros(),
+        checked.overhead_vs(eventchains)
+    );
+}
+
+fn print_tier3_report(results: &[(usize, ComprehensiveMetrics)]) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "TIER 3: Middleware Scaling - Cost per Middleware Layer"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
+
+    println!("{}", MIDDLEWARE_THRESHOLDS.legend("Middleware overhead").dimmed());
+
+    let baseline = &results[0].1; // 0 middleware is baseline
+
+    println!("\n{}", "⏱️  Timing Scaling".yellow().bold());
+    println!("{}", "-".repeat(90));
+    println!(
+        "{:<25} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "Middleware Count".bold(),
+        "Mean (μs)".bold(),
+        "Overhead %".bold(),
+        "Per MW (μs)".bold(),
+        "Memory (KB)".bold(),
+        "Allocs/Run".bold()
+    );
+    println!("{}", "-".repeat(90));
+
+    for (count, metrics) in results {
+        let overhead = if *count == 0 {
+            0.0
+        } else {
+            metrics.overhead_vs(baseline)
+        };
+
+        let per_mw = if *count > 0 {
+            (metrics.mean_micros() - baseline.mean_micros()) / (*count as f64)
+        } else {
+            0.0
+        };
+
+        let color = MIDDLEWARE_THRESHOLDS.color_for(overhead);
+
+        let (memory, allocs) = if metrics.memory_tracked {
+            (
+                format!("{:.2}", metrics.memory_stats.peak_memory as f64 / 1024.0),
+                format!("{:.1}", metrics.allocations_per_run),
+            )
+        } else {
+            ("-".to_string(), "-".to_string())
+        };
+
+        println!(
+            "{:<25} {:>12.2} {:>12} {:>12.3} {:>12} {:>12}",
+            format!("{} middleware", count),
+            metrics.mean_micros(),
+            if *count == 0 {
+                "baseline".to_string()
+            } else {
+                format!("+{:.2}%", overhead).color(color).to_string()
+            },
+            per_mw,
+            memory,
+            allocs
+        );
+    }
+
+    println!("\n{}", "🚀 Throughput by Middleware Count".yellow().bold());
+    println!("{}", "-".repeat(90));
+    println!(
+        "{:<25} {:>20} {:>20}",
+        "Middleware Count".bold(),
+        "Throughput (paths/s)".bold(),
+        "Degradation".bold()
+    );
+    println!("{}", "-".repeat(90));
+
+    for (count, metrics) in results {
+        let baseline_throughput = baseline.throughput_per_sec();
+        let degradation = if baseline_throughput > 0.0 {
+            (1.0 - metrics.throughput_per_sec() / baseline_throughput) * 100.0
+        } else {
+            0.0
+        };
+
+        println!(
+            "{:<25} {:>20.0} {:>20}",
+            format!("{} middleware", count),
+            metrics.throughput_per_sec(),
+            if *count == 0 {
+                "baseline".to_string()
+            } else {
+                format!("{:+.2}%", -degradation)
+            }
+        );
+    }
+
+    println!("\n{}", "📈 Linear Fit (mean μs over middleware count)".yellow().bold());
+    println!("{}", "-".repeat(90));
+    let points: Vec<(f64, f64)> = results
+        .iter()
+        .map(|(count, metrics)| (*count as f64, metrics.mean_micros()))
+        .collect();
+    match LinearFit::fit(&points) {
+        Some(fit) => {
+            println!("  Slope:     {:.3} μs per middleware layer", fit.slope);
+            println!("  Intercept: {:.2} μs", fit.intercept);
+            let verdict = if fit.r_squared >= LINEARITY_R_SQUARED_THRESHOLD {
+                "linear".green()
+            } else {
+                "poor fit - super-linear scaling, or noise larger than the per-layer cost".red()
+            };
+            println!("  R²:        {:.4} ({})", fit.r_squared, verdict);
+        }
+        None => println!("  Not enough distinct middleware counts to fit"),
+    }
+
+    println!("\n{}", "📊 Latency Variance by Middleware Count".yellow().bold());
+    println!("{}", "-".repeat(90));
+    println!(
+        "{:<25} {:>15} {:>15} {:>15}",
+        "Middleware Count".bold(),
+        "Std Dev (μs)".bold(),
+        "CoV (%)".bold(),
+        "P99 (μs)".bold()
+    );
+    println!("{}", "-".repeat(90));
+
+    for (count, metrics) in results {
+        println!(
+            "{:<25} {:>15.2} {:>15.2} {:>15.2}",
+            format!("{} middleware", count),
+            metrics.std_dev_nanos / 1000.0,
+            metrics.coefficient_of_variation(),
+            metrics.p99_duration.as_nanos() as f64 / 1000.0
+        );
+    }
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    println!("  - Per-middleware cost shows if overhead scales linearly");
+    println!("  - Latency variance indicates performance predictability");
+    println!("  - Memory scaling shows allocation patterns");
+}
+
+fn print_overhead_knee(knee: Option<usize>, threshold_pct: f64, max_count: usize) {
+    println!("\n{}", "📍 Overhead Knee".yellow().bold());
+    println!("{}", "-".repeat(90));
+    match knee {
+        Some(count) => println!(
+            "  Overhead first exceeds {:.0}% at {} middleware layers",
+            threshold_pct, count
+        ),
+        None => println!(
+            "  Overhead stays below {:.0}% up to {} middleware layers",
+            threshold_pct, max_count
+        ),
+    }
+}
+
+fn print_tier4_report(baseline: &ComprehensiveMetrics, eventchains: &ComprehensiveMetrics) {
+    print_comprehensive_comparison(
+        "TIER 4: Real-World Scenario - Cost vs Equivalent Manual Work",
+        baseline,
+        eventchains,
+    );
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    println!("  This tier shows real-world performance:");
+    println!("  - EventChains vs manual logging and timing");
+    println!("  - Trade-off between convenience and performance");
+    println!("  - Value of consistent middleware API");
+}
+
+fn print_dispatch_report(results: &[(usize, ComprehensiveMetrics, ComprehensiveMetrics)]) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "TIER 5: Dispatch Strategy - Recursive vs Pre-composed Middleware"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
+    println!("{}", TIMING_THRESHOLDS.legend("Iterative vs recursive").dimmed());
+
+    println!(
+        "\n{:<20} {:>18} {:>18} {:>18}",
+        "Middleware Count".bold(),
+        "Recursive (μs)".bold(),
+        "Iterative (μs)".bold(),
+        "Difference %".bold()
+    );
+    println!("{}", "-".repeat(90));
+
+    for (count, recursive, iterative) in results {
+        let difference = iterative.overhead_vs(recursive);
+        println!(
+            "{:<20} {:>18.2} {:>18.2} {:>18}",
+            count,
+            recursive.mean_micros(),
+            iterative.mean_micros(),
+            format!("{:+.2}%", difference).color(TIMING_THRESHOLDS.color_for(difference))
+        );
+    }
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    println!("  - Both strategies run the same no-op middleware, so only dispatch differs");
+    println!("  - Recursive dispatch builds a closure per layer for every event");
+    println!("  - Iterative dispatch composes the stack once per chain execution");
+}
+
+fn print_static_dispatch_report(
+    dispatch: &(ComprehensiveMetrics, ComprehensiveMetrics),
+    tier2: &(ComprehensiveMetrics, ComprehensiveMetrics),
+) {
+    let (dynamic, fixed) = dispatch;
+    print_comprehensive_comparison(
+        "TIER 7: Static Dispatch - Box<dyn> Events vs Enum Events (no executor)",
+        fixed,
+        dynamic,
+    );
+
+    // Tier 2's gap also includes the executor and context; this is the part
+    // that only boxing and vtable calls account for
+    let vtable_cost = dynamic.mean_micros() - fixed.mean_micros();
+    let abstraction_cost = tier2.1.mean_micros() - tier2.0.mean_micros();
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    if vtable_cost <= 0.0 {
+        println!("  - Enum dispatch was no faster: four vtable calls per query are below the noise");
+        return;
+    }
+    println!(
+        "  - Boxing and dynamic dispatch cost {:.3}μs per query",
+        vtable_cost
+    );
+    if abstraction_cost > 0.0 {
+        let share = vtable_cost / abstraction_cost * 100.0;
+        println!(
+            "  - That is {:.1}% of Tier 2's {:.3}μs abstraction overhead",
+            share, abstraction_cost
+        );
+        if share < 100.0 {
+            println!("  - The rest comes from the executor and the type-erased context");
+        }
+    }
+}
+
+fn print_unit_grid_report(results: &[(&'static str, ComprehensiveMetrics); 3]) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "TIER 6: Unit-Weight Grid - Framework Overhead vs Algorithmic Floor"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
+
+    let [(_, bfs), (_, dijkstra), (_, eventchains)] = results;
+
+    println!(
+        "\n{:<35} {:>12} {:>12} {:>12} {:>12}",
+        "Search".bold(),
+        "Mean (μs)".bold(),
+        "Median (μs)".bold(),
+        "P99 (μs)".bold(),
+        "vs BFS".bold()
+    );
+    println!("{}", "-".repeat(90));
+
+    for (label, metrics) in results {
+        println!(
+            "{:<35} {:>12.2} {:>12.2} {:>12.2} {:>11.2}x",
+            label,
+            metrics.mean_micros(),
+            metrics.median_duration.as_nanos() as f64 / 1000.0,
+            metrics.p99_duration.as_nanos() as f64 / 1000.0,
+            metrics.mean_micros() / bfs.mean_micros()
+        );
+    }
+
+    // Time EventChains adds over the same Dijkstra, against the time the
+    // cheapest correct algorithm needs for the whole query
+    let framework_overhead = eventchains.mean_micros() - dijkstra.mean_micros();
+    let algorithmic_floor = bfs.mean_micros();
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    println!(
+        "  - EventChains adds {:.2}μs over bare Dijkstra; BFS answers the query in {:.2}μs",
+        framework_overhead, algorithmic_floor
+    );
+    if framework_overhead > algorithmic_floor {
+        println!(
+            "  - {}",
+            "The framework overhead dwarfs the algorithmic cost on unit-weight graphs".red()
+        );
+    } else {
+        println!(
+            "  - {}",
+            "The framework overhead stays below the algorithmic cost".green()
+        );
+    }
+}
+
+fn print_chain_construction_report(construction: &ComprehensiveMetrics, execution: &ComprehensiveMetrics) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "Chain Construction - Cost of Building the Pipeline"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
+
+    let share = if execution.mean_micros() > 0.0 {
+        construction.mean_micros() / execution.mean_micros() * 100.0
+    } else {
+        0.0
+    };
+
+    println!(
+        "{:<40} {:>15} {:>15} {:>15}",
+        "Phase".bold(),
+        "Mean (μs)".bold(),
+        "Median (μs)".bold(),
+        "Allocations".bold()
+    );
+    println!("{}", "-".repeat(90));
+    println!(
+        "{:<40} {:>15.3} {:>15.3} {:>15}",
+        "Construction (4 events + 3 middleware)",
+        construction.mean_micros(),
+        construction.median_duration.as_nanos() as f64 / 1000.0,
+        construction.memory_stats.allocation_count
+    );
+    println!(
+        "{:<40} {:>15.3} {:>15.3} {:>15}",
+        "Full run (logging + timing)",
+        execution.mean_micros(),
+        execution.median_duration.as_nanos() as f64 / 1000.0,
+        execution.memory_stats.allocation_count
+    );
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    println!("  Construction accounts for {:.2}% of a full query", share);
+    println!("  - Short-lived queries pay this on every call");
+    println!("  - Reusing a built chain amortizes it away");
+}
+
+fn print_graph_construction_report(construction: &ComprehensiveMetrics, query: &ComprehensiveMetrics) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "Graph Construction - Setup vs Query Cost"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
+
+    println!(
+        "{:<40} {:>15} {:>15} {:>15}",
+        "Phase".bold(),
+        "Mean (μs)".bold(),
+        "Bytes/run".bold(),
+        "Allocs/run".bold()
+    );
+    println!("{}", "-".repeat(90));
+    println!(
+        "{:<40} {:>15.3} {:>15.0} {:>15.1}",
+        "Graph::random_connected",
+        construction.mean_micros(),
+        construction.bytes_allocated_per_run,
+        construction.allocations_per_run
+    );
+    println!(
+        "{:<40} {:>15.3} {:>15.0} {:>15.1}",
+        "One query (Tier 1 EventChains)",
+        query.mean_micros(),
+        query.bytes_allocated_per_run,
+        query.allocations_per_run
+    );
+
+    println!("\n{}", "Interpretation:".yellow().bold());
+    if query.mean_micros() > 0.0 {
+        println!(
+            "  Building the graph costs as much as {:.1} queries",
+            construction.mean_micros() / query.mean_micros()
+        );
+    }
+    println!("  - Generate once and reuse the graph across queries");
+    println!("  - Budget setup separately when graphs are short-lived");
+}
+
+fn print_context_cost_report(arc: &ComprehensiveMetrics, state: &ComprehensiveMetrics) {
+    print_comprehensive_comparison(
+        &format!(
+            "Context Lookup Cost - Arc<Graph> vs DijkstraState clone ({} lookups per run)",
+            CONTEXT_LOOKUPS_PER_RUN
+        ),
+        arc,
+        state,
+    );
+
+    let per_lookup_nanos =
+        |metrics: &ComprehensiveMetrics| metrics.mean_duration.as_nanos() as f64 / CONTEXT_LOOKUPS_PER_RUN as f64;
+    let per_lookup_bytes =
+        |metrics: &ComprehensiveMetrics| metrics.bytes_allocated_per_run / CONTEXT_LOOKUPS_PER_RUN as f64;
+
+    println!("\n{}", "Per Lookup:".yellow().bold());
+    for (label, metrics) in [("Arc<Graph>", arc), ("DijkstraState", state)] {
+        if metrics.memory_tracked {
+            println!(
+                "  {:<15} {:>10.1} ns {:>12.0} bytes",
+                label,
+                per_lookup_nanos(metrics),
+                per_lookup_bytes(metrics)
+            );
+        } else {
+            println!("  {:<15} {:>10.1} ns", label, per_lookup_nanos(metrics));
+        }
+    }
+    println!("  - Every per-node event pays the state clone; the graph lookup is negligible");
+}
+
+fn print_pq_comparison_report(results: &[(&str, ComprehensiveMetrics)]) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "Priority Queues - Cost of the Data Structure (Tier 1 baseline)"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
+    println!("{}", TIMING_THRESHOLDS.legend("Timing overhead").dimmed());
+
+    let baseline = &results[0].1;
+
+    println!(
+        "\n{:<35} {:>12} {:>12} {:>12} {:>12}",
+        "Queue".bold(),
+        "Mean (μs)".bold(),
+        "Median (μs)".bold(),
+        "P99 (μs)".bold(),
+        "vs Heap".bold()
+    );
+    println!("{}", "-".repeat(90));
+
+    for (i, (label, metrics)) in results.iter().enumerate() {
+        let relative = if i == 0 {
+            "baseline".to_string()
+        } else {
+            let overhead = metrics.overhead_vs(baseline);
+            format!("{:+.2}%", overhead)
+                .color(TIMING_THRESHOLDS.color_for(overhead))
+                .to_string()
+        };
+
+        println!(
+            "{:<35} {:>12.2} {:>12.2} {:>12.2} {:>12}",
+            label,
+            metrics.mean_micros(),
+            metrics.median_duration.as_nanos() as f64 / 1000.0,
+            metrics.p99_duration.as_nanos() as f64 / 1000.0,
+            relative
+        );
+    }
+}
+
+fn print_middleware_catalogue_report(baseline: &ComprehensiveMetrics, results: &[(&str, ComprehensiveMetrics)]) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "Middleware Catalogue - One Middleware vs None (Tier 1 EventChains)"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
+    println!("{}", TIMING_THRESHOLDS.legend("Timing overhead").dimmed());
+
+    println!(
+        "\n{:<35} {:>12} {:>12} {:>12} {:>12}",
+        "Middleware".bold(),
+        "Mean (μs)".bold(),
+        "Median (μs)".bold(),
+        "Allocs/run".bold(),
+        "Overhead %".bold()
     );
+    println!("{}", "-".repeat(90));
 
-    println!("\n{}", "Interpretation:".yellow().bold());
-    println!("  This tier shows the pure cost of the EventChains framework:");
-    println!("  - Event wrapping and trait dispatch overhead");
-    println!("  - Context creation and type erasure costs");
-    println!("  - Result enum wrapping impact");
-    println!("  - Memory allocations from the framework");
+    for (label, metrics) in results {
+        let overhead = metrics.overhead_vs(baseline);
+        println!(
+            "{:<35} {:>12.2} {:>12.2} {:>12.1} {:>12}",
+            label,
+            metrics.mean_micros(),
+            metrics.median_duration.as_nanos() as f64 / 1000.0,
+            metrics.allocations_per_run,
+            format!("{:+.2}%", overhead).color(TIMING_THRESHOLDS.color_for(overhead))
+        );
+    }
 }
 
-fn print_tier2_report(baseline: &ComprehensiveMetrics, eventchains: &ComprehensiveMetrics) {
-    print_comprehensive_comparison(
-        "TIER 2: Feature-Parity Baseline - Cost of Abstraction",
-        baseline,
-        eventchains,
+fn print_event_memory_report(deltas: &[(String, i64)]) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "Per-Event Memory - Net Bytes Left Allocated by Each Event (one EventChains run)"
+            .bright_cyan()
+            .bold()
     );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
 
-    println!("\n{}", "Interpretation:".yellow().bold());
-    println!("  This tier shows the cost of abstraction:");
-    println!("  - Generic trait-based design vs concrete types");
-    println!("  - Dynamic dispatch vs static dispatch");
-    println!("  - Type-erased context vs typed variables");
+    println!("\n{:<35} {:>15}", "Event".bold(), "Net bytes".bold());
+    println!("{}", "-".repeat(90));
+    for (event, bytes) in deltas {
+        println!("{:<35} {:>15}", event, bytes);
+    }
+    println!("  - Values stored in the context count toward the event that stored them");
 }
 
-fn print_tier3_report(results: &[(usize, ComprehensiveMetrics)]) {
+fn print_workload_report(results: &[(QueryBias, f64, ComprehensiveMetrics, ComprehensiveMetrics)]) {
     println!("\n{}", "=".repeat(90).bright_cyan().bold());
     println!(
         "{}",
-        "TIER 3: Middleware Scaling - Cost per Middleware Layer"
+        format!("Query Workloads - {} Queries per Run by Target Bias", WORKLOAD_QUERIES)
             .bright_cyan()
             .bold()
     );
     println!("{}", "=".repeat(90).bright_cyan().bold());
+    println!("{}", TIMING_THRESHOLDS.legend("Timing overhead").dimmed());
 
-    let baseline = &results[0].1; // 0 middleware is baseline
-
-    println!("\n{}", "⏱️  Timing Scaling".yellow().bold());
-    println!("{}", "-".repeat(90));
     println!(
-        "{:<25} {:>12} {:>12} {:>12} {:>15}",
-        "Middleware Count".bold(),
-        "Mean (μs)".bold(),
-        "Overhead %".bold(),
-        "Per MW (μs)".bold(),
-        "Memory (KB)".bold()
+        "\n{:<20} {:>10} {:>16} {:>16} {:>12}",
+        "Workload".bold(),
+        "Mean hops".bold(),
+        "Baseline (μs)".bold(),
+        "EventChains (μs)".bold(),
+        "Overhead %".bold()
     );
     println!("{}", "-".repeat(90));
 
-    for (count, metrics) in results {
-        let overhead = if *count == 0 {
-            0.0
-        } else {
-            metrics.overhead_vs(baseline)
-        };
+    for (bias, hops, baseline, eventchains) in results {
+        let overhead = eventchains.overhead_vs(baseline);
+        println!(
+            "{:<20} {:>10.2} {:>16.2} {:>16.2} {:>12}",
+            bias.label(),
+            hops,
+            baseline.mean_micros(),
+            eventchains.mean_micros(),
+            format!("{:+.2}%", overhead).color(TIMING_THRESHOLDS.color_for(overhead))
+        );
+    }
+    println!("  - Longer paths spread the per-query framework cost over more settled nodes");
+}
 
-        let per_mw = if *count > 0 {
-            (metrics.mean_micros() - baseline.mean_micros()) / (*count as f64)
-        } else {
-            0.0
-        };
+fn print_fault_tolerance_report(results: &[(FaultToleranceMode, ChainStatus, ComprehensiveMetrics)]) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
+    println!(
+        "{}",
+        "Fault Tolerance - Cost of a Failing Query per Mode"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
 
-        let color = if overhead < 30.0 {
-            "green"
-        } else if overhead < 60.0 {
-            "yellow"
-        } else {
-            "red"
-        };
+    println!(
+        "\n{:<15} {:<25} {:>12} {:>12} {:>15}",
+        "Mode".bold(),
+        "Chain Status".bold(),
+        "Mean (μs)".bold(),
+        "Median (μs)".bold(),
+        "Allocs/Run".bold()
+    );
+    println!("{}", "-".repeat(90));
 
+    for (mode, status, metrics) in results {
         println!(
-            "{:<25} {:>12.2} {:>12} {:>12.3} {:>15.2}",
-            format!("{} middleware", count),
+            "{:<15} {:<25} {:>12.2} {:>12.2} {:>15.1}",
+            format!("{:?}", mode),
+            status.to_string(),
             metrics.mean_micros(),
-            if *count == 0 {
-                "baseline".to_string()
-            } else {
-                format!("+{:.2}%", overhead).color(color).to_string()
-            },
-            per_mw,
-            metrics.memory_stats.peak_memory as f64 / 1024.0
+            metrics.median_duration.as_nanos() as f64 / 1000.0,
+            metrics.allocations_per_run
         );
     }
+    println!("  - The source is out of range, so InitializeState fails in every mode");
+}
 
-    println!("\n{}", "📊 Latency Variance by Middleware Count".yellow().bold());
-    println!("{}", "-".repeat(90));
+fn print_batch_sweep_report(baseline: &ComprehensiveMetrics, results: &[(usize, ComprehensiveMetrics)]) {
+    println!("\n{}", "=".repeat(90).bright_cyan().bold());
     println!(
-        "{:<25} {:>15} {:>15} {:>15}",
-        "Middleware Count".bold(),
-        "Std Dev (μs)".bold(),
-        "CoV (%)".bold(),
-        "P99 (μs)".bold()
+        "{}",
+        "Batching - Nodes Settled per Event vs Tier 1 Baseline"
+            .bright_cyan()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_cyan().bold());
+    println!("{}", TIMING_THRESHOLDS.legend("Timing overhead").dimmed());
+
+    println!(
+        "\n{:<25} {:>12} {:>12} {:>12} {:>12}",
+        "Batch Size".bold(),
+        "Mean (μs)".bold(),
+        "Median (μs)".bold(),
+        "P99 (μs)".bold(),
+        "Overhead %".bold()
     );
     println!("{}", "-".repeat(90));
 
-    for (count, metrics) in results {
+    for (batch_size, metrics) in results {
+        let overhead = metrics.overhead_vs(baseline);
         println!(
-            "{:<25} {:>15.2} {:>15.2} {:>15.2}",
-            format!("{} middleware", count),
-            metrics.std_dev_nanos / 1000.0,
-            metrics.coefficient_of_variation(),
-            metrics.p99_duration.as_nanos() as f64 / 1000.0
+            "{:<25} {:>12.2} {:>12.2} {:>12.2} {:>12}",
+            batch_size,
+            metrics.mean_micros(),
+            metrics.median_duration.as_nanos() as f64 / 1000.0,
+            metrics.p99_duration.as_nanos() as f64 / 1000.0,
+            format!("{:+.2}%", overhead).color(TIMING_THRESHOLDS.color_for(overhead))
         );
     }
 
     println!("\n{}", "Interpretation:".yellow().bold());
-    println!("  - Per-middleware cost shows if overhead scales linearly");
-    println!("  - Latency variance indicates performance predictability");
-    println!("  - Memory scaling shows allocation patterns");
-}
-
-fn print_tier4_report(baseline: &ComprehensiveMetrics, eventchains: &ComprehensiveMetrics) {
-    print_comprehensive_comparison(
-        "TIER 4: Real-World Scenario - Cost vs Equivalent Manual Work",
-        baseline,
-        eventchains,
-    );
-
-    println!("\n{}", "Interpretation:".yellow().bold());
-    println!("  This tier shows real-world performance:");
-    println!("  - EventChains vs manual logging and timing");
-    println!("  - Trade-off between convenience and performance");
-    println!("  - Value of consistent middleware API");
+    println!("  - Each event execution clones the queue and state out of the context");
+    println!("  - Larger batches amortize that round-trip over more settled nodes");
 }
 
 fn print_executive_summary(
@@ -350,7 +1798,467 @@ fn print_executive_summary(
     println!("\n{}", "=".repeat(90).bright_magenta().bold());
 }
 
-fn main() {
+/// Every measurement taken for one test case, reported once all are done
+struct TestCaseResults {
+    case: BenchConfig,
+    tier1: (ComprehensiveMetrics, ComprehensiveMetrics),
+    result_wrapped: ComprehensiveMetrics,
+    tier2: (ComprehensiveMetrics, ComprehensiveMetrics),
+    checked: ComprehensiveMetrics,
+    tier3: Vec<(usize, ComprehensiveMetrics)>,
+    knee: Option<usize>,
+    tier4: (ComprehensiveMetrics, ComprehensiveMetrics),
+    dispatch: Vec<(usize, ComprehensiveMetrics, ComprehensiveMetrics)>,
+    unit_grid: [(&'static str, ComprehensiveMetrics); 3],
+    scale_free: (ComprehensiveMetrics, ComprehensiveMetrics),
+    static_dispatch: (ComprehensiveMetrics, ComprehensiveMetrics),
+    construction: ComprehensiveMetrics,
+    graph_construction: ComprehensiveMetrics,
+    layout: (ComprehensiveMetrics, ComprehensiveMetrics),
+    caches: (ComprehensiveMetrics, ComprehensiveMetrics),
+    context_cost: (ComprehensiveMetrics, ComprehensiveMetrics),
+    distance_only: (ComprehensiveMetrics, ComprehensiveMetrics),
+    queues: Vec<(&'static str, ComprehensiveMetrics)>,
+    batches: Vec<(usize, ComprehensiveMetrics)>,
+    middleware: Vec<(&'static str, ComprehensiveMetrics)>,
+    event_memory: Option<Vec<(String, i64)>>,
+    workloads: Vec<(QueryBias, f64, ComprehensiveMetrics, ComprehensiveMetrics)>,
+    fault_tolerance: Vec<(FaultToleranceMode, ChainStatus, ComprehensiveMetrics)>,
+    #[cfg(feature = "hugepages")]
+    hugepages: (ComprehensiveMetrics, ComprehensiveMetrics),
+}
+
+/// Every tier measurement of a test case with a stable display name, as used
+/// for memory fingerprints and saved baselines
+fn labeled_benchmarks(results: &TestCaseResults) -> Vec<(String, &ComprehensiveMetrics)> {
+    let mut labeled = vec![
+        ("Tier 1: bare function calls".to_string(), &results.tier1.0),
+        ("Tier 1: EventChains (no middleware)".to_string(), &results.tier1.1),
+        ("Tier 1: bare + EventResult wrapping".to_string(), &results.result_wrapped),
+        ("Tier 2: manual instrumented".to_string(), &results.tier2.0),
+        ("Tier 2: EventChains (no middleware)".to_string(), &results.tier2.1),
+        ("Tier 2: EventChains (baseline fallback)".to_string(), &results.checked),
+        ("Tier 4: manual (logging + timing)".to_string(), &results.tier4.0),
+        ("Tier 4: EventChains (logging + timing)".to_string(), &results.tier4.1),
+    ];
+    for (count, metrics) in &results.tier3 {
+        labeled.push((format!("Tier 3: {} middleware", count), metrics));
+    }
+    for (batch_size, metrics) in &results.batches {
+        labeled.push((format!("Batched: {} nodes per event", batch_size), metrics));
+    }
+    for (label, metrics) in &results.middleware {
+        labeled.push((format!("Middleware: {}", label), metrics));
+    }
+    for (bias, _, baseline, eventchains) in &results.workloads {
+        labeled.push((format!("Workload {}: bare function calls", bias.label()), baseline));
+        labeled.push((format!("Workload {}: EventChains", bias.label()), eventchains));
+    }
+    for (mode, _, metrics) in &results.fault_tolerance {
+        labeled.push((format!("Fault tolerance: {:?}", mode), metrics));
+    }
+    for (count, recursive, iterative) in &results.dispatch {
+        labeled.push((format!("Tier 5: {} middleware (recursive)", count), recursive));
+        labeled.push((format!("Tier 5: {} middleware (iterative)", count), iterative));
+    }
+    for (label, metrics) in &results.unit_grid {
+        labeled.push((format!("Tier 6: {}", label), metrics));
+    }
+    labeled.push(("Scale-free: bare function calls".to_string(), &results.scale_free.0));
+    labeled.push(("Scale-free: EventChains".to_string(), &results.scale_free.1));
+    labeled.push(("Tier 7: boxed dyn events".to_string(), &results.static_dispatch.0));
+    labeled.push(("Tier 7: enum events".to_string(), &results.static_dispatch.1));
+    labeled.push(("Graph construction".to_string(), &results.graph_construction));
+    labeled.push(("Distance only: bare function calls".to_string(), &results.distance_only.0));
+    labeled.push(("Distance only: EventChains".to_string(), &results.distance_only.1));
+    labeled
+}
+
+/// Check that both dispatch strategies nest the Tier 5 middleware the same
+/// way, so their benchmarks time the same work
+fn verify_dispatch_order(graph: &Arc<Graph>, source: NodeId, target: NodeId) {
+    let labels = ["A", "B", "C"];
+    let recursive = middleware_call_order(graph.clone(), source, target, &labels, DispatchStrategy::Recursive);
+    let iterative = middleware_call_order(graph.clone(), source, target, &labels, DispatchStrategy::Iterative);
+
+    if recursive == iterative {
+        println!("  Middleware nesting identical under recursive and iterative dispatch ✓");
+    } else {
+        eprintln!(
+            "{} recursive dispatch ran middleware as {:?}, iterative as {:?}",
+            "Warning:".yellow().bold(),
+            recursive,
+            iterative
+        );
+    }
+}
+
+fn print_test_case_header(case: &BenchConfig, graph: &Arc<Graph>) {
+    println!(
+        "\n\n{}",
+        format!(
+            "=== TEST CASE: {} nodes, {} edges, {} runs ===",
+            case.nodes, case.edges, case.runs
+        )
+        .bright_cyan()
+        .bold()
+    );
+
+    let source = NodeId(0);
+    let target = NodeId(case.nodes - 1);
+
+    println!("\n{}", "Graph generated successfully!".green());
+    println!("  Source node: {}", source.0);
+    println!("  Target node: {}", target.0);
+
+    let stats = graph.stats();
+    println!("  Adjacency entries: {}", stats.edges);
+    println!(
+        "  Degree: min {} / mean {:.2} / max {}",
+        stats.min_degree, stats.mean_degree, stats.max_degree
+    );
+    println!(
+        "  Connected: {}",
+        if stats.connected { "yes".green() } else { "no".red() }
+    );
+    verify_paths(graph, source, target);
+    verify_dispatch_order(graph, source, target);
+}
+
+/// Run all benchmarks for one test case
+fn run_test_case(case: BenchConfig, graph: Arc<Graph>, defaults: &BenchmarkOptions) -> TestCaseResults {
+    let source = NodeId(0);
+    let target = NodeId(case.nodes - 1);
+    let runs = case.runs;
+    let options = &defaults.clone().with_warmup(case.warmup);
+
+    let tier1 = run_tier1_comprehensive(graph.clone(), source, target, runs, options);
+    let result_wrapped = run_result_wrapping_benchmark(graph.clone(), source, target, runs, options);
+    let tier2 = run_tier2_comprehensive(graph.clone(), source, target, runs, options);
+    let checked = run_checked_benchmark(graph.clone(), source, target, runs, options);
+    let tier3 = run_tier3_comprehensive(graph.clone(), source, target, runs, options);
+    progress_start("  Searching for the middleware overhead knee...");
+    let knee = find_overhead_knee(graph.clone(), source, target, KNEE_THRESHOLD_PCT, KNEE_MAX_COUNT, options);
+    progress_done();
+    let tier4 = run_tier4_comprehensive(graph.clone(), source, target, runs, options);
+    let dispatch = run_dispatch_comparison(graph.clone(), source, target, runs, options);
+    let unit_grid = run_unit_grid_comparison(case.nodes, runs, options);
+    let scale_free = run_scale_free_comparison(&case, runs, options);
+    let static_dispatch = run_static_dispatch_comparison(graph.clone(), source, target, runs, options);
+    let construction = bench_chain_construction(runs, case.nodes, options);
+    let graph_construction = run_graph_construction_benchmark(case.nodes, case.edges, options);
+    let layout = run_csr_comparison(graph.clone(), source, target, runs, options);
+    let caches = run_cache_comparison(graph.clone(), source, target, runs, options);
+    let context_cost = run_context_cost_analysis(graph.clone(), runs, options);
+    let distance_only = run_distance_only_comparison(graph.clone(), source, target, runs, options);
+    let queues = run_pq_comparison(graph.clone(), source, target, runs, options);
+    let batches = run_batch_sweep(graph.clone(), source, target, runs, options);
+    let middleware = run_middleware_catalogue(graph.clone(), source, target, runs, options);
+    let event_memory = profile_event_memory(graph.clone(), source, target);
+    let workloads = run_workload_comparison(graph.clone(), runs, options);
+    let fault_tolerance = run_fault_tolerance_comparison(graph.clone(), target, runs, options);
+    #[cfg(feature = "hugepages")]
+    let hugepages = run_hugepage_comparison(graph.clone(), source, target, runs, options);
+
+    TestCaseResults {
+        case,
+        tier1,
+        result_wrapped,
+        tier2,
+        checked,
+        tier3,
+        knee,
+        tier4,
+        dispatch,
+        unit_grid,
+        scale_free,
+        static_dispatch,
+        construction,
+        graph_construction,
+        layout,
+        caches,
+        context_cost,
+        distance_only,
+        queues,
+        batches,
+        middleware,
+        event_memory,
+        workloads,
+        fault_tolerance,
+        #[cfg(feature = "hugepages")]
+        hugepages,
+    }
+}
+
+/// Run each test case on its own thread, returning the results in the order
+/// given. Disables memory tracking for the rest of the process first, as
+/// concurrent benchmarks cannot be measured apart.
+fn run_test_cases_parallel(prepared: &[(BenchConfig, Arc<Graph>)], defaults: &BenchmarkOptions) -> Vec<TestCaseResults> {
+    disable_memory_tracking();
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = prepared
+            .iter()
+            .map(|(case, graph)| scope.spawn(move || run_test_case(*case, graph.clone(), defaults)))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+fn report_test_case(results: &TestCaseResults, options: &CliOptions, verdicts: &mut Vec<TierVerdict>) {
+    let TestCaseResults {
+        case,
+        tier1,
+        result_wrapped,
+        tier2,
+        checked,
+        tier3,
+        knee,
+        tier4,
+        dispatch,
+        unit_grid,
+        scale_free,
+        static_dispatch,
+        construction,
+        graph_construction,
+        layout,
+        caches,
+        context_cost,
+        distance_only,
+        queues,
+        batches,
+        middleware,
+        event_memory,
+        workloads,
+        fault_tolerance,
+        ..
+    } = results;
+
+    // Print detailed reports
+    print_tier1_report(&tier1.0, &tier1.1, result_wrapped);
+    print_tier2_report(&tier2.0, &tier2.1, checked);
+    print_tier3_report(tier3);
+    print_overhead_knee(*knee, KNEE_THRESHOLD_PCT, KNEE_MAX_COUNT);
+    print_tier4_report(&tier4.0, &tier4.1);
+    print_dispatch_report(dispatch);
+    print_unit_grid_report(unit_grid);
+    print_comprehensive_comparison(
+        &format!(
+            "Scale-Free Graph (Barabási–Albert, m = {}) - Tier 1 Baseline vs EventChains",
+            scale_free_attachments(case.nodes, case.edges)
+        ),
+        &scale_free.0,
+        &scale_free.1,
+    );
+    print_static_dispatch_report(static_dispatch, tier2);
+    print_chain_construction_report(construction, &tier4.1);
+    print_graph_construction_report(graph_construction, &tier1.1);
+
+    print_comprehensive_comparison(
+        "Memory Layout - Vec<Vec<Edge>> vs CSR Adjacency (Tier 1 baseline)",
+        &layout.0,
+        &layout.1,
+    );
+    print_comprehensive_comparison(
+        "Cache Policy - Warm vs Cold Caches (Tier 1 EventChains)",
+        &caches.0,
+        &caches.1,
+    );
+    print_context_cost_report(&context_cost.0, &context_cost.1);
+    print_comprehensive_comparison(
+        "Distance Only - Full Path vs Distance Only (Tier 1 baseline)",
+        &tier1.0,
+        &distance_only.0,
+    );
+    print_comprehensive_comparison(
+        "Distance Only - Full Path vs Distance Only (Tier 1 EventChains)",
+        &tier1.1,
+        &distance_only.1,
+    );
+    print_pq_comparison_report(queues);
+    print_batch_sweep_report(&tier1.0, batches);
+    print_middleware_catalogue_report(&tier1.1, middleware);
+    if let Some(deltas) = event_memory {
+        print_event_memory_report(deltas);
+    }
+    print_workload_report(workloads);
+    print_fault_tolerance_report(fault_tolerance);
+
+    #[cfg(feature = "hugepages")]
+    print_comprehensive_comparison(
+        "Huge Page Hints - Effect of MADV_HUGEPAGE on State Vectors (best-effort)",
+        &results.hugepages.0,
+        &results.hugepages.1,
+    );
+
+    // Print executive summary
+    print_executive_summary(
+        (&tier1.0, &tier1.1),
+        (&tier2.0, &tier2.1),
+        tier3,
+        (&tier4.0, &tier4.1),
+    );
+
+    if memory_tracking_enabled() {
+        let fingerprints: Vec<MemoryFingerprint> = labeled_benchmarks(results)
+            .into_iter()
+            .map(|(label, metrics)| MemoryFingerprint::from_metrics(&label, metrics))
+            .collect();
+        print_memory_fingerprints(&fingerprints);
+    }
+
+    if let Some(threshold) = options.fail_on_regression {
+        let case = case.label();
+        let t3_baseline = &tier3[0].1;
+        let t3_five = &tier3.iter().find(|(c, _)| *c == 5).unwrap().1;
+        verdicts.push(TierVerdict::evaluate(
+            &case,
+            "Tier 1: framework",
+            &tier1.0,
+            &tier1.1,
+            threshold,
+        ));
+        verdicts.push(TierVerdict::evaluate(
+            &case,
+            "Tier 2: abstraction",
+            &tier2.0,
+            &tier2.1,
+            threshold,
+        ));
+        verdicts.push(TierVerdict::evaluate(
+            &case,
+            "Tier 3: 5 middleware",
+            t3_baseline,
+            t3_five,
+            threshold,
+        ));
+        verdicts.push(TierVerdict::evaluate(
+            &case,
+            "Tier 4: real-world",
+            &tier4.0,
+            &tier4.1,
+            threshold,
+        ));
+    }
+}
+
+fn record_baseline(results: &TestCaseResults, baseline: &mut BaselineFile) {
+    let case = results.case.label();
+    for (label, metrics) in labeled_benchmarks(results) {
+        baseline
+            .entries
+            .push(BaselineEntry::from_metrics(&case, &label, metrics));
+    }
+}
+
+/// Write every benchmark of a test case under `dir`, one criterion group
+/// per test case
+fn export_criterion(results: &TestCaseResults, dir: &Path) -> Result<(), String> {
+    let group = results.case.label();
+    for (label, metrics) in labeled_benchmarks(results) {
+        write_criterion_benchmark(dir, &group, &label, metrics)?;
+    }
+    Ok(())
+}
+
+/// Handle `--cross-check`: compare every implementation over `seeds` random
+/// graphs per size instead of benchmarking
+fn run_cross_check(seeds: u64) -> ExitCode {
+    println!(
+        "Cross-checking {} implementations over {} seeds x {} graph sizes...",
+        cross_check::variant_count(),
+        seeds,
+        cross_check::CROSS_CHECK_SIZES.len()
+    );
+    match cross_check::cross_check(seeds) {
+        Ok(queries) => {
+            println!("  All implementations agree on {} queries ✓", queries);
+            ExitCode::SUCCESS
+        }
+        Err(divergence) => {
+            eprintln!("{} {}", "Divergence:".red().bold(), divergence);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Load the DIMACS graph at `path` into `cache` as the single test case to
+/// run, keyed so that `get_or_generate` returns it instead of a random graph
+fn load_dimacs_case(path: &Path, cache: &mut GraphCache) -> Result<BenchConfig, String> {
+    let file = std::fs::File::open(path).map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+    let graph = Graph::from_dimacs(std::io::BufReader::new(file))
+        .map_err(|err| format!("invalid DIMACS graph {}: {}", path.display(), err))?;
+    if graph.nodes == 0 {
+        return Err(format!("DIMACS graph {} has no nodes", path.display()));
+    }
+
+    let case = BenchConfig::new(graph.nodes, graph.stats().edges, DEFAULT_BENCH_RUNS);
+    cache.insert(case.nodes, case.edges, case.seed, Arc::new(graph));
+    Ok(case)
+}
+
+fn main() -> ExitCode {
+    let options = match CliOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if options.no_color || no_color_requested() {
+        colored::control::set_override(false);
+    }
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    if let Some(seeds) = options.cross_check {
+        return run_cross_check(seeds);
+    }
+
+    let mut graph_cache = GraphCache::new(100);
+
+    let mut test_cases = if let Some(path) = &options.dimacs {
+        match load_dimacs_case(path, &mut graph_cache) {
+            Ok(case) => vec![case],
+            Err(err) => {
+                eprintln!("error: {}", err);
+                return ExitCode::from(2);
+            }
+        }
+    } else {
+        match options.config.as_deref().map(BenchConfig::load_all) {
+            Some(Ok(configs)) => configs,
+            Some(Err(err)) => {
+                eprintln!("error: {}", err);
+                return ExitCode::from(2);
+            }
+            None => BenchConfig::defaults(),
+        }
+    };
+    if let Some(warmup) = options.warmup {
+        for case in &mut test_cases {
+            case.warmup = warmup;
+        }
+    }
+
+    let saved_baseline = match options.baseline.as_deref().map(BaselineFile::load).transpose() {
+        Ok(saved) => saved,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::from(2);
+        }
+    };
+    let mut current = BaselineFile {
+        parallel: options.parallel,
+        ..BaselineFile::default()
+    };
+    if let Some(saved) = &saved_baseline {
+        if let Err(err) = current.check_comparable(saved) {
+            eprintln!("error: {}", err);
+            return ExitCode::from(2);
+        }
+    }
+
     println!("{}", "=".repeat(90).bright_magenta().bold());
     println!(
         "{}",
@@ -361,53 +2269,252 @@ fn main() {
     println!("{}", "=".repeat(90).bright_magenta().bold());
     println!("\n{}", "Measuring: CPU, Memory, Cache Behavior, and Latency Variance".bright_yellow());
 
-    // Test configuration
-    let test_cases = vec![
-        (100, 500, 100),   // Small graph - more runs
-        (500, 2500, 50),   // Medium graph
-        (1000, 5000, 30),  // Large graph
-        (2000, 10000, 20), // Extra large graph
-    ];
+    let mut verdicts = Vec::new();
 
-    for (nodes, edges, runs) in test_cases {
+    let defaults = options.benchmark_options();
+    if options.parallel {
         println!(
-            "\n\n{}",
-            format!("=== TEST CASE: {} nodes, {} edges, {} runs ===", nodes, edges, runs)
-                .bright_cyan()
-                .bold()
+            "\n{} running test cases in parallel; memory tracking is disabled",
+            "Warning:".yellow().bold()
         );
+        SHOW_PROGRESS.store(false, Ordering::Relaxed);
 
-        // Generate graph
-        let graph = Arc::new(Graph::random_connected(nodes, edges, 100));
-        let source = NodeId(0);
-        let target = NodeId(nodes - 1);
-
-        println!("\n{}", "Graph generated successfully!".green());
-        println!("  Source node: {}", source.0);
-        println!("  Target node: {}", target.0);
-
-        // Run all tier benchmarks
-        let tier1 = run_tier1_comprehensive(graph.clone(), source, target, runs);
-        let tier2 = run_tier2_comprehensive(graph.clone(), source, target, runs);
-        let tier3 = run_tier3_comprehensive(graph.clone(), source, target, runs);
-        let tier4 = run_tier4_comprehensive(graph.clone(), source, target, runs);
-
-        // Print detailed reports
-        print_tier1_report(&tier1.0, &tier1.1);
-        print_tier2_report(&tier2.0, &tier2.1);
-        print_tier3_report(&tier3);
-        print_tier4_report(&tier4.0, &tier4.1);
-
-        // Print executive summary
-        print_executive_summary(
-            (&tier1.0, &tier1.1),
-            (&tier2.0, &tier2.1),
-            &tier3,
-            (&tier4.0, &tier4.1),
-        );
+        let prepared: Vec<_> = test_cases
+            .iter()
+            .map(|&case| {
+                let graph = graph_cache.get_or_generate(case.nodes, case.edges, case.seed);
+                (case, graph)
+            })
+            .collect();
+
+        println!("\n{}", "Running all test cases...".bright_yellow().bold());
+        let results = run_test_cases_parallel(&prepared, &defaults);
+
+        for (results, (_, graph)) in results.iter().zip(&prepared) {
+            print_test_case_header(&results.case, graph);
+            report_test_case(results, &options, &mut verdicts);
+            record_baseline(results, &mut current);
+            if let Some(dir) = &options.criterion_out {
+                if let Err(err) = export_criterion(results, dir) {
+                    eprintln!("error: {}", err);
+                    return ExitCode::from(2);
+                }
+            }
+        }
+    } else {
+        for case in test_cases {
+            let graph = graph_cache.get_or_generate(case.nodes, case.edges, case.seed);
+            print_test_case_header(&case, &graph);
+            let results = run_test_case(case, graph, &defaults);
+            report_test_case(&results, &options, &mut verdicts);
+            record_baseline(&results, &mut current);
+            if let Some(dir) = &options.criterion_out {
+                if let Err(err) = export_criterion(&results, dir) {
+                    eprintln!("error: {}", err);
+                    return ExitCode::from(2);
+                }
+            }
+        }
     }
 
+    let (dynamic, fixed) = run_fixed_state_comparison();
+    print_fixed_state_report(&dynamic, &fixed);
+
     println!("\n{}", "=".repeat(90).bright_magenta().bold());
     println!("{}", "All Comprehensive Benchmarks Complete!".bright_green().bold());
     println!("{}", "=".repeat(90).bright_magenta().bold());
+    println!("  Graphs generated: {} (shared by every tier of a test case)", graph_cache.len());
+
+    if let Some(path) = &options.stream_jsonl {
+        let graph = graph_cache.get_or_generate(100, 500, DEFAULT_SEED);
+        match run_streamed_benchmark(graph, path) {
+            Ok(metrics) => print_streamed_report(&metrics),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    if let Some(path) = &options.save_baseline {
+        match current.save(path) {
+            Ok(()) => println!("\nSaved baseline to {}", path.display()),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let mut passed = true;
+
+    if let Some(threshold) = options.fail_on_regression {
+        passed &= print_verdict_summary(&verdicts, threshold);
+    }
+
+    if let Some(saved) = &saved_baseline {
+        let deltas = compare_to_baseline(saved, &current, options.threshold_percent, options.gate_statistic);
+        passed &= print_baseline_comparison(&deltas, options.threshold_percent, options.gate_statistic);
+        let changes = compare_allocations(saved, &current, PEAK_MEMORY_TOLERANCE_PCT);
+        passed &= print_allocation_comparison(&changes);
+    }
+
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dijkstra_events::InitializeStateEvent;
+    use eventchains::{ChainableEvent, EventChain, EventContext, EventResult};
+
+    #[test]
+    fn chain_construction_is_measured() {
+        let _lock = bench_lock();
+        let metrics = bench_chain_construction(50, 100, &BenchmarkOptions::default());
+
+        assert_eq!(metrics.runs, 50);
+        assert!(metrics.mean_duration > Duration::ZERO);
+        // Four boxed events and three boxed middleware per chain at least
+        assert!(metrics.allocations_per_run >= 7.0);
+    }
+
+    #[test]
+    fn graph_construction_allocations_grow_with_the_graph() {
+        let _lock = bench_lock();
+        let small = run_graph_construction_benchmark(500, 2_000, &BenchmarkOptions::default());
+        let large = run_graph_construction_benchmark(2_000, 8_000, &BenchmarkOptions::default());
+
+        assert_eq!(small.runs, GRAPH_CONSTRUCTION_RUNS);
+        // Four times the nodes and edges: one adjacency list per node and
+        // the edge set both scale linearly
+        let bytes = large.bytes_allocated_per_run / small.bytes_allocated_per_run;
+        let allocations = large.allocations_per_run / small.allocations_per_run;
+        assert!((3.5..4.5).contains(&bytes), "bytes x{}", bytes);
+        assert!((3.5..4.5).contains(&allocations), "allocations x{}", allocations);
+    }
+
+    #[test]
+    fn tier1_baseline_allocates_near_the_theoretical_minimum() {
+        let _lock = bench_lock();
+        let graph = Arc::new(Graph::random_connected_seeded(200, 800, 100, 6));
+        let (source, target) = (NodeId(0), NodeId(199));
+
+        let baseline = run_comprehensive_benchmark(20, || {
+            path_found(dijkstra_tier1_baseline(graph.clone(), source, target))
+        });
+        let bare = run_comprehensive_benchmark(20, || {
+            path_found(dijkstra_eventchains_bare(graph.clone(), source, target))
+        });
+
+        // Heap and path growth account for the baseline's few extra
+        // allocations; the bare chain moves the queue back into the context
+        // on every node
+        assert!(baseline.allocation_multiple() <= 4.0, "{}", baseline.allocation_multiple());
+        assert!(bare.allocation_multiple() >= 20.0, "{}", bare.allocation_multiple());
+    }
+
+    #[test]
+    fn test_case_options_reach_every_benchmark() {
+        let _lock = bench_lock();
+        let case = BenchConfig { warmup: 0, ..BenchConfig::new(20, 60, 3) };
+        let graph = Arc::new(Graph::random_connected_seeded(case.nodes, case.edges, 100, case.seed));
+        let results = run_test_case(case, graph, &BenchmarkOptions::new().with_trim(10.0));
+
+        for (label, metrics) in labeled_benchmarks(&results) {
+            assert_eq!(metrics.trim_percent, 10.0, "{}", label);
+        }
+        // The cache comparison layers its policy on top of the same options
+        assert_eq!(results.caches.1.trim_percent, 10.0);
+    }
+
+    #[test]
+    fn state_lookups_allocate_far_more_than_graph_lookups() {
+        let _lock = bench_lock();
+        let graph = Arc::new(Graph::random_connected_seeded(1_000, 4_000, 100, 3));
+        let (arc, state) = run_context_cost_analysis(graph, 5, &BenchmarkOptions::default());
+
+        // Cloning an Arc only bumps its count; cloning the state copies a
+        // distance, predecessor and visited flag per node
+        assert_eq!(arc.bytes_allocated_per_run, 0.0);
+        let state_bytes = (CONTEXT_LOOKUPS_PER_RUN * 1_000 * (4 + 16 + 1)) as f64;
+        assert!(state.bytes_allocated_per_run >= state_bytes, "{}", state.bytes_allocated_per_run);
+    }
+
+    /// Busy-waits around every event, standing in for costly middleware
+    struct SpinMiddleware;
+
+    impl EventMiddleware for SpinMiddleware {
+        fn execute(
+            &self,
+            _event: &dyn ChainableEvent,
+            context: &mut EventContext,
+            next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+        ) -> EventResult<()> {
+            let start = std::time::Instant::now();
+            while start.elapsed() < Duration::from_micros(50) {}
+            next(context)
+        }
+    }
+
+    #[test]
+    fn knee_is_found_for_expensive_middleware() {
+        let _lock = bench_lock();
+        let knee = find_knee(KNEE_THRESHOLD_PCT, KNEE_MAX_COUNT, &BenchmarkOptions::default(), |count| {
+            let mut chain = EventChain::new();
+            for _ in 0..count {
+                chain.use_middleware(Box::new(SpinMiddleware));
+            }
+            chain.add_event(Box::new(InitializeStateEvent::new(NodeId(0), 10)));
+            let mut context = EventContext::new();
+            if chain.execute(&mut context).success {
+                Ok(())
+            } else {
+                Err("chain failed".to_string())
+            }
+        });
+        assert!(matches!(knee, Some(count) if count < KNEE_MAX_COUNT), "{:?}", knee);
+    }
+
+    #[test]
+    fn parallel_runs_keep_the_sequential_ordering() {
+        let _lock = bench_lock();
+        // Sizes far enough apart that preemption by the other threads, which
+        // may share a single CPU, cannot reorder the medians
+        let prepared: Vec<(BenchConfig, Arc<Graph>)> = [(400, 1200), (10, 20), (60, 180)]
+            .into_iter()
+            .map(|(nodes, edges)| {
+                let mut case = BenchConfig::new(nodes, edges, 15);
+                case.warmup = 0;
+                (case, Arc::new(Graph::random_connected_seeded(nodes, edges, 100, case.seed)))
+            })
+            .collect();
+        let defaults = BenchmarkOptions::default();
+        let order = |results: &[TestCaseResults]| {
+            let mut order: Vec<usize> = (0..results.len()).collect();
+            order.sort_by_key(|&i| results[i].tier1.0.median_duration);
+            order
+        };
+
+        let sequential: Vec<TestCaseResults> = prepared
+            .iter()
+            .map(|(case, graph)| run_test_case(*case, graph.clone(), &defaults))
+            .collect();
+        let parallel = {
+            let _restore = RestoreMemoryTracking::new();
+            run_test_cases_parallel(&prepared, &defaults)
+        };
+
+        assert!(sequential.iter().all(|results| results.tier1.0.memory_tracked));
+        assert!(parallel.iter().all(|results| !results.tier1.0.memory_tracked));
+        for (results, (case, _)) in parallel.iter().zip(&prepared) {
+            assert_eq!(results.case, *case);
+        }
+        assert_eq!(order(&sequential), vec![1, 2, 0]);
+        assert_eq!(order(&parallel), order(&sequential));
+    }
 }
@@ -1,5 +1,12 @@
+use crate::comprehensive_benchmarking::MemoryStats;
 use crate::eventchains::{ChainableEvent, EventContext, EventMiddleware, EventResult};
-use std::time::Instant;
+use crate::graph::{NodeId, ShortestPathResult};
+use hashbrown::HashMap;
+use serde::Serialize;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 /// Logging middleware that tracks event execution
 pub struct LoggingMiddleware {
@@ -29,6 +36,7 @@ impl EventMiddleware for LoggingMiddleware {
             match &result {
                 EventResult::Success(_) => println!("    * {} completed", event.name()),
                 EventResult::Failure(err) => println!("    X {} failed: {}", event.name(), err),
+                EventResult::Abort(err) => println!("    X {} aborted the chain: {}", event.name(), err),
             }
         }
 
@@ -88,6 +96,7 @@ impl PerformanceMiddleware {
         }
     }
 
+    #[allow(dead_code, reason = "the count is for inspection; benchmarks only measure the middleware's cost")]
     pub fn get_event_count(&self) -> u64 {
         *self.event_count.lock().unwrap()
     }
@@ -114,3 +123,994 @@ impl Default for PerformanceMiddleware {
         Self::new()
     }
 }
+
+/// Timeout middleware that fails events exceeding a time budget.
+///
+/// The executor is synchronous and cannot interrupt a running event, so the
+/// budget is checked after `next` returns: a slow event still runs to
+/// completion, but its success is replaced with a failure. Failures and
+/// aborts are passed through unchanged, so a slow event that aborted still
+/// stops the chain.
+pub struct TimeoutMiddleware {
+    pub budget: Duration,
+}
+
+impl TimeoutMiddleware {
+    pub fn new(budget: Duration) -> Self {
+        Self { budget }
+    }
+}
+
+impl EventMiddleware for TimeoutMiddleware {
+    fn execute(
+        &self,
+        _event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        let start = Instant::now();
+
+        let result = next(context);
+
+        if result.is_success() && start.elapsed() > self.budget {
+            return EventResult::Failure("event exceeded timeout".to_string());
+        }
+
+        result
+    }
+}
+
+/// Per-event failure bookkeeping for the circuit breaker
+#[derive(Debug, Clone, Default)]
+pub struct FailureState {
+    pub consecutive_failures: u32,
+    pub opened_at: Option<Instant>,
+}
+
+/// Circuit-breaker middleware keyed on event name.
+///
+/// After `failure_threshold` consecutive failures of an event, further
+/// executions of that event fail immediately with "circuit open" until
+/// `cooldown` has elapsed. The next execution after the cooldown is let
+/// through; a success closes the circuit, a failure re-opens it.
+pub struct CircuitBreakerMiddleware {
+    failure_threshold: u32,
+    cooldown: Duration,
+    states: Arc<Mutex<HashMap<String, FailureState>>>,
+}
+
+impl CircuitBreakerMiddleware {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+            states: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Shared handle to the failure states, for inspection across chains
+    #[allow(dead_code, reason = "for inspecting breaker state across chains; the driver never trips it")]
+    pub fn states(&self) -> Arc<Mutex<HashMap<String, FailureState>>> {
+        self.states.clone()
+    }
+
+    #[allow(dead_code, reason = "for inspecting breaker state across chains; the driver never trips it")]
+    pub fn is_open(&self, event_name: &str) -> bool {
+        let states = self.states.lock().unwrap();
+        states
+            .get(event_name)
+            .and_then(|s| s.opened_at)
+            .is_some_and(|opened| opened.elapsed() < self.cooldown)
+    }
+}
+
+impl EventMiddleware for CircuitBreakerMiddleware {
+    fn execute(
+        &self,
+        event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        {
+            let mut states = self.states.lock().unwrap();
+            let state = states.entry(event.name().to_string()).or_default();
+            if let Some(opened) = state.opened_at {
+                if opened.elapsed() < self.cooldown {
+                    return EventResult::Failure("circuit open".to_string());
+                }
+                // Cooldown elapsed: let one attempt through (half-open)
+                state.opened_at = None;
+            }
+        }
+
+        let result = next(context);
+
+        let mut states = self.states.lock().unwrap();
+        let state = states.entry(event.name().to_string()).or_default();
+        if result.is_failure() {
+            state.consecutive_failures += 1;
+            if state.consecutive_failures >= self.failure_threshold {
+                state.opened_at = Some(Instant::now());
+            }
+        } else {
+            state.consecutive_failures = 0;
+        }
+
+        result
+    }
+}
+
+/// Shared cache of finalized results keyed on `(source, target)`
+pub type PathCache = Arc<Mutex<HashMap<(NodeId, NodeId), ShortestPathResult>>>;
+
+/// Middleware that memoizes the `FinalizeResult` event on `(source, target)`.
+///
+/// On a cache hit the cached `ShortestPathResult` is written to the context's
+/// "result" key and the event is skipped; on a miss the event runs and its
+/// result is cached. The key is read from the context's "source" and "target"
+/// entries, so the caller must set "target"; other events, and chains
+/// without a "target", pass through untouched.
+///
+/// Only use this when the graph is immutable across runs: the cache is not
+/// keyed on the graph, so a mutated graph would be served stale paths.
+pub struct MemoizingMiddleware {
+    cache: PathCache,
+}
+
+impl MemoizingMiddleware {
+    pub fn new() -> Self {
+        Self::with_cache(Arc::new(Mutex::new(HashMap::new())))
+    }
+
+    /// Memoize into an existing cache, so results survive across chains
+    pub fn with_cache(cache: PathCache) -> Self {
+        Self { cache }
+    }
+
+    pub fn cache(&self) -> PathCache {
+        self.cache.clone()
+    }
+
+    /// Drop cached results made stale by changing the weight of edge
+    /// `from -> to` (as returned by `Graph::update_edge_weight`). A heavier
+    /// edge only invalidates paths through it, in either direction; a
+    /// lighter one can create a shorter path between any pair, so the whole
+    /// cache is cleared.
+    pub fn invalidate_edge(&self, from: NodeId, to: NodeId, old_weight: u32, new_weight: u32) {
+        let mut cache = self.cache.lock().unwrap();
+        if new_weight < old_weight {
+            cache.clear();
+        } else if new_weight > old_weight {
+            cache.retain(|_, result| {
+                !result
+                    .segments()
+                    .any(|hop| hop == (from, to) || hop == (to, from))
+            });
+        }
+    }
+}
+
+impl Default for MemoizingMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventMiddleware for MemoizingMiddleware {
+    fn execute(
+        &self,
+        event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        if event.name() != "FinalizeResult" {
+            return next(context);
+        }
+
+        let key = match (context.get::<NodeId>("source"), context.get::<NodeId>("target")) {
+            (Some(source), Some(target)) => (source, target),
+            _ => return next(context),
+        };
+
+        let cached = self.cache.lock().unwrap().get(&key).cloned();
+        if let Some(result) = cached {
+            context.set("result", result);
+            return EventResult::Success(());
+        }
+
+        let result = next(context);
+
+        if result.is_success() {
+            if let Some(path) = context.get::<ShortestPathResult>("result") {
+                self.cache.lock().unwrap().insert(key, path);
+            }
+        }
+
+        result
+    }
+}
+
+/// Net bytes allocated per event name, accumulated across executions
+pub type EventMemoryDeltas = Arc<Mutex<HashMap<String, i64>>>;
+
+/// Opt-in middleware that attributes net allocation (bytes allocated minus
+/// bytes freed) to each event by snapshotting `MemoryStats` around `next`.
+///
+/// Everything that runs inside `next` is charged to the wrapped event,
+/// including middleware nested inside this one; register it first so it is
+/// innermost and measures the event alone. Nested events (such as the body
+/// of a `RepeatUntilEvent`) are charged under the wrapping event's name.
+/// The allocator counters are process-wide, so deltas are only meaningful
+/// while no other thread allocates.
+pub struct MemoryProfilingMiddleware {
+    deltas: EventMemoryDeltas,
+}
+
+impl MemoryProfilingMiddleware {
+    pub fn new() -> Self {
+        Self {
+            deltas: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn deltas(&self) -> EventMemoryDeltas {
+        self.deltas.clone()
+    }
+}
+
+impl Default for MemoryProfilingMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventMiddleware for MemoryProfilingMiddleware {
+    fn execute(
+        &self,
+        event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        let before = MemoryStats::snapshot();
+        let result = next(context);
+        let after = MemoryStats::snapshot();
+
+        // From the raw counters: `net_allocated` saturates at zero once a
+        // counter reset leaves more bytes freed than allocated
+        let allocated = after.total_allocated as i64 - before.total_allocated as i64;
+        let freed = after.total_deallocated as i64 - before.total_deallocated as i64;
+        let delta = allocated - freed;
+        *self
+            .deltas
+            .lock()
+            .unwrap()
+            .entry(event.name().to_string())
+            .or_insert(0) += delta;
+
+        result
+    }
+}
+
+/// Middleware that fails events allocating more than `max_bytes`, measured
+/// as the growth of `MemoryStats::total_allocated` across `next`.
+///
+/// Like `TimeoutMiddleware`, it cannot stop an event mid-allocation: the
+/// event runs to completion and its result is then replaced with a failure.
+/// Bytes are counted when allocated, whether or not they are freed before
+/// `next` returns, and growing a `Vec` counts each reallocation in full.
+/// The counters come from `TrackingAllocator`, so the budget never trips
+/// unless it is the global allocator, and they are process-wide: any
+/// allocation made by another thread meanwhile is charged to this event.
+pub struct MemoryBudgetMiddleware {
+    pub max_bytes: usize,
+}
+
+impl MemoryBudgetMiddleware {
+    pub fn new(max_bytes: usize) -> Self {
+        Self { max_bytes }
+    }
+}
+
+impl EventMiddleware for MemoryBudgetMiddleware {
+    fn execute(
+        &self,
+        _event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        let before = MemoryStats::snapshot().total_allocated;
+
+        let result = next(context);
+
+        // Saturating: a benchmark starting inside `next` resets the counters
+        let allocated = MemoryStats::snapshot().total_allocated.saturating_sub(before);
+        if allocated > self.max_bytes {
+            return EventResult::Failure("memory budget exceeded".to_string());
+        }
+
+        result
+    }
+}
+
+/// One executed event in a `ChainReplay`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayEntry {
+    pub event_name: String,
+    pub success: bool,
+    /// Nanoseconds from the replay's creation to the event finishing
+    pub timestamp_ns: u64,
+}
+
+/// Trace of executed events recorded by `ReplayMiddleware`, shared with the
+/// middleware so it can be read after the chain runs
+#[derive(Clone)]
+pub struct ChainReplay {
+    entries: Arc<Mutex<Vec<ReplayEntry>>>,
+    start: Instant,
+}
+
+impl ChainReplay {
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(Vec::new())),
+            start: Instant::now(),
+        }
+    }
+
+    #[allow(dead_code, reason = "the driver only measures the recording cost; tests read the trace back")]
+    pub fn entries(&self) -> Vec<ReplayEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    #[allow(dead_code, reason = "the driver only measures the recording cost; tests read the trace back")]
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// The trace as a pretty-printed JSON array, oldest entry first
+    #[allow(dead_code, reason = "the driver only measures the recording cost; tests read the trace back")]
+    pub fn dump(&self) -> String {
+        let entries = self.entries.lock().unwrap();
+        serde_json::to_string_pretty(&*entries).expect("replay entries always serialize")
+    }
+}
+
+impl Default for ChainReplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Middleware that records every executed event, its outcome and when it
+/// finished, for reproducing a failing run. Entries are appended as events
+/// complete, so a nested event (such as the body of a `RepeatUntilEvent`)
+/// is listed before the event wrapping it.
+pub struct ReplayMiddleware {
+    replay: ChainReplay,
+}
+
+impl ReplayMiddleware {
+    pub fn new() -> Self {
+        Self::with_replay(ChainReplay::new())
+    }
+
+    /// Record into an existing replay, so one trace can span several chains
+    pub fn with_replay(replay: ChainReplay) -> Self {
+        Self { replay }
+    }
+
+    #[allow(dead_code, reason = "the driver only measures the recording cost; tests read the trace back")]
+    pub fn replay(&self) -> ChainReplay {
+        self.replay.clone()
+    }
+}
+
+impl Default for ReplayMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventMiddleware for ReplayMiddleware {
+    fn execute(
+        &self,
+        event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        let result = next(context);
+
+        let entry = ReplayEntry {
+            event_name: event.name().to_string(),
+            success: result.is_success(),
+            timestamp_ns: self.replay.start.elapsed().as_nanos() as u64,
+        };
+        self.replay.entries.lock().unwrap().push(entry);
+
+        result
+    }
+}
+
+/// Side-effect-only hooks run around each event by
+/// `ParallelObserverMiddleware`. Observers see the context read-only and
+/// cannot change the event's result.
+pub trait Observer: Send + Sync {
+    fn before(&self, event_name: &str, context: &EventContext);
+    fn after(&self, event_name: &str, context: &EventContext, result: &EventResult<()>);
+}
+
+/// Hook of one phase as handed to the observer workers, with its lifetime
+/// erased; `run_all` waits for every worker to finish with it
+struct HookJob(*const (dyn Fn(&dyn Observer) + Sync + 'static));
+
+// SAFETY: the hook is `Sync`, and `run_all` keeps it alive until each worker
+// has reported back
+unsafe impl Send for HookJob {}
+
+/// Middleware that runs every observer's `before` hook concurrently, then
+/// the event, then every `after` hook concurrently.
+///
+/// Only for observers that don't depend on each other or on running in a
+/// fixed order, such as exporting metrics over the network; anything that
+/// writes to the context belongs in an ordinary `EventMiddleware`. Every
+/// observer after the first has its own worker thread, started with the
+/// middleware and joined when it is dropped, so a phase costs two channel
+/// round trips per worker rather than a thread spawn. Events executed
+/// concurrently through one middleware take turns at each phase.
+pub struct ParallelObserverMiddleware {
+    observers: Arc<[Box<dyn Observer>]>,
+    workers: Vec<mpsc::Sender<HookJob>>,
+    handles: Vec<JoinHandle<()>>,
+    /// Outcome of each worker's hook; held for a whole phase
+    done: Mutex<mpsc::Receiver<thread::Result<()>>>,
+}
+
+impl ParallelObserverMiddleware {
+    pub fn new(observers: Vec<Box<dyn Observer>>) -> Self {
+        let observers: Arc<[Box<dyn Observer>]> = observers.into();
+        let (done_sender, done) = mpsc::channel();
+        let (mut workers, mut handles) = (Vec::new(), Vec::new());
+
+        for index in 1..observers.len() {
+            let (sender, jobs) = mpsc::channel::<HookJob>();
+            let (observers, done) = (observers.clone(), done_sender.clone());
+            let handle = thread::Builder::new()
+                .name(format!("observer-{}", index))
+                .spawn(move || {
+                    for HookJob(hook) in jobs {
+                        // SAFETY: `run_all` waits for this outcome before the
+                        // hook goes out of scope
+                        let hook = unsafe { &*hook };
+                        let outcome = panic::catch_unwind(AssertUnwindSafe(|| hook(observers[index].as_ref())));
+                        if done.send(outcome).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .expect("cannot spawn an observer worker");
+            workers.push(sender);
+            handles.push(handle);
+        }
+
+        Self {
+            observers,
+            workers,
+            handles,
+            done: Mutex::new(done),
+        }
+    }
+
+    /// Call `hook` on every observer, the first on this thread and the rest
+    /// on their workers, rethrowing the first panic once all have finished
+    fn run_all(&self, hook: impl Fn(&dyn Observer) + Sync) {
+        let Some(first) = self.observers.first() else {
+            return;
+        };
+        let done = self.done.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let hook: &(dyn Fn(&dyn Observer) + Sync) = &hook;
+        // SAFETY: only dereferenced by workers before they report back below
+        let erased: *const (dyn Fn(&dyn Observer) + Sync + 'static) = unsafe { std::mem::transmute(hook) };
+        let sent = self
+            .workers
+            .iter()
+            .filter(|worker| worker.send(HookJob(erased)).is_ok())
+            .count();
+
+        let mut panicked = panic::catch_unwind(AssertUnwindSafe(|| hook(first.as_ref()))).err();
+        for _ in 0..sent {
+            match done.recv() {
+                Ok(Ok(())) => {}
+                Ok(Err(payload)) => {
+                    panicked.get_or_insert(payload);
+                }
+                // Every worker has exited, so none still holds the hook
+                Err(_) => break,
+            }
+        }
+        drop(done);
+
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+impl Drop for ParallelObserverMiddleware {
+    fn drop(&mut self) {
+        // Closing the job channels ends each worker's loop
+        self.workers.clear();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl EventMiddleware for ParallelObserverMiddleware {
+    fn execute(
+        &self,
+        event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        let name = event.name();
+
+        let shared: &EventContext = context;
+        self.run_all(|observer| observer.before(name, shared));
+
+        let result = next(context);
+
+        let shared: &EventContext = context;
+        self.run_all(|observer| observer.after(name, shared, &result));
+
+        result
+    }
+}
+
+/// Middleware that wraps each event in a `tracing` span named "event",
+/// recording its duration and outcome as span fields
+#[cfg(feature = "tracing")]
+#[allow(dead_code, reason = "for use under an external subscriber; the driver installs none")]
+pub struct TracingMiddleware;
+
+#[cfg(feature = "tracing")]
+impl EventMiddleware for TracingMiddleware {
+    fn execute(
+        &self,
+        event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        let span = tracing::info_span!(
+            "event",
+            name = event.name(),
+            duration_ns = tracing::field::Empty,
+            success = tracing::field::Empty,
+        );
+        let _entered = span.enter();
+
+        let start = Instant::now();
+        let result = next(context);
+
+        span.record("duration_ns", start.elapsed().as_nanos() as u64);
+        span.record("success", result.is_success());
+        if let Some(err) = result.get_error() {
+            tracing::warn!(error = %err, aborted = result.is_abort(), "event failed");
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comprehensive_benchmarking::{bench_lock, BenchmarkGuard};
+    use crate::eventchains::{ChainStatus, EventChain};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Event that sleeps for `delay`, then returns `outcome`
+    struct ScriptedEvent {
+        delay: Duration,
+        outcome: fn() -> EventResult<()>,
+    }
+
+    impl ChainableEvent for ScriptedEvent {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            std::thread::sleep(self.delay);
+            (self.outcome)()
+        }
+
+        fn name(&self) -> &str {
+            "Scripted"
+        }
+    }
+
+    fn run_with(middleware: Box<dyn EventMiddleware>, event: ScriptedEvent) -> EventResult<()> {
+        let mut context = EventContext::new();
+        middleware.execute(&event, &mut context, &mut |ctx| event.execute(ctx))
+    }
+
+    #[test]
+    fn circuit_breaker_opens_after_the_threshold_and_closes_after_the_cooldown() {
+        let breaker = CircuitBreakerMiddleware::new(2, Duration::from_millis(30));
+        let calls = std::cell::Cell::new(0);
+        let run = |outcome: EventResult<()>| {
+            let event = ScriptedEvent {
+                delay: Duration::ZERO,
+                outcome: || EventResult::Success(()),
+            };
+            breaker.execute(&event, &mut EventContext::new(), &mut |_| {
+                calls.set(calls.get() + 1);
+                outcome.clone()
+            })
+        };
+
+        run(EventResult::Failure("boom".to_string()));
+        assert!(!breaker.is_open("Scripted"));
+        run(EventResult::Failure("boom".to_string()));
+        assert!(breaker.is_open("Scripted"));
+
+        // Open: short-circuited without calling the event
+        let result = run(EventResult::Success(()));
+        assert!(matches!(result, EventResult::Failure(msg) if msg == "circuit open"));
+        assert_eq!(calls.get(), 2);
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(!breaker.is_open("Scripted"));
+        assert!(run(EventResult::Success(())).is_success());
+        assert_eq!(calls.get(), 3);
+        assert_eq!(breaker.states().lock().unwrap()["Scripted"].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn timeout_fails_a_slow_success() {
+        let event = ScriptedEvent {
+            delay: Duration::from_millis(20),
+            outcome: || EventResult::Success(()),
+        };
+        let result = run_with(Box::new(TimeoutMiddleware::new(Duration::from_millis(1))), event);
+        assert!(matches!(result, EventResult::Failure(msg) if msg == "event exceeded timeout"));
+    }
+
+    #[test]
+    fn timeout_passes_a_fast_success() {
+        let event = ScriptedEvent {
+            delay: Duration::ZERO,
+            outcome: || EventResult::Success(()),
+        };
+        let result = run_with(Box::new(TimeoutMiddleware::new(Duration::from_secs(10))), event);
+        assert!(result.is_success());
+    }
+
+    #[test]
+    fn timeout_keeps_a_slow_abort() {
+        let event = ScriptedEvent {
+            delay: Duration::from_millis(20),
+            outcome: || EventResult::Abort("stop".to_string()),
+        };
+        let mut chain = EventChain::new();
+        chain.use_middleware(Box::new(TimeoutMiddleware::new(Duration::from_millis(1))));
+        chain.add_event(Box::new(event));
+
+        let result = chain.execute(&mut EventContext::new());
+        assert_eq!(result.status, ChainStatus::Aborted);
+        assert_eq!(result.failures[0].error_message, "stop");
+    }
+
+    /// Fields recorded on an "event" span, formatted with `Debug` except
+    /// for strings
+    #[cfg(feature = "tracing")]
+    #[derive(Default)]
+    struct SpanFields(Vec<(String, String)>);
+
+    #[cfg(feature = "tracing")]
+    impl SpanFields {
+        fn get(&self, name: &str) -> Option<&str> {
+            self.0.iter().find(|(field, _)| field == name).map(|(_, value)| value.as_str())
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for SpanFields {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.push((field.name().to_string(), value.to_string()));
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+
+    /// Layer that collects the fields of every "event" span once it closes
+    #[cfg(feature = "tracing")]
+    struct CapturedSpans(Arc<Mutex<Vec<SpanFields>>>);
+
+    #[cfg(feature = "tracing")]
+    impl<S> tracing_subscriber::Layer<S> for CapturedSpans
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if attrs.metadata().name() == "event" {
+                let mut fields = SpanFields::default();
+                attrs.record(&mut fields);
+                ctx.span(id).unwrap().extensions_mut().insert(fields);
+            }
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if let Some(fields) = ctx.span(id).unwrap().extensions_mut().get_mut::<SpanFields>() {
+                values.record(fields);
+            }
+        }
+
+        fn on_close(&self, id: tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            if let Some(fields) = ctx.span(&id).unwrap().extensions_mut().remove::<SpanFields>() {
+                self.0.lock().unwrap().push(fields);
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_middleware_opens_a_span_per_event() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        struct Named(&'static str, fn() -> EventResult<()>);
+
+        impl ChainableEvent for Named {
+            fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+                (self.1)()
+            }
+
+            fn name(&self) -> &str {
+                self.0
+            }
+        }
+
+        let mut chain = EventChain::new();
+        chain.use_middleware(Box::new(TracingMiddleware));
+        chain.add_event(Box::new(Named("First", || EventResult::Success(()))));
+        chain.add_event(Box::new(Named("Second", || EventResult::Failure("broken".to_string()))));
+
+        let spans = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(CapturedSpans(spans.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(!chain.execute(&mut EventContext::new()).success);
+        });
+
+        let spans = spans.lock().unwrap();
+        assert_eq!(spans.len(), 2);
+        for (span, (name, success)) in spans.iter().zip([("First", "true"), ("Second", "false")]) {
+            assert_eq!(span.get("name"), Some(name));
+            assert_eq!(span.get("success"), Some(success));
+            assert!(span.get("duration_ns").and_then(|ns| ns.parse::<u64>().ok()).is_some());
+        }
+    }
+
+    /// `FinalizeResult` stand-in that counts its executions
+    struct CountingFinalize(AtomicUsize);
+
+    impl ChainableEvent for CountingFinalize {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            context.set("result", ShortestPathResult::unreachable(NodeId(0), NodeId(1)));
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            "FinalizeResult"
+        }
+    }
+
+    #[test]
+    fn memoizing_hit_skips_the_inner_event() {
+        let memoizing = MemoizingMiddleware::new();
+        let event = CountingFinalize(AtomicUsize::new(0));
+        let run = |target: NodeId| {
+            let mut context = EventContext::new();
+            context.set("source", NodeId(0));
+            context.set("target", target);
+            let result = memoizing.execute(&event, &mut context, &mut |ctx| event.execute(ctx));
+            assert!(result.is_success());
+            assert!(context.has("result"));
+        };
+
+        run(NodeId(1));
+        run(NodeId(1));
+        assert_eq!(event.0.load(Ordering::SeqCst), 1);
+
+        // A different key misses
+        run(NodeId(2));
+        assert_eq!(event.0.load(Ordering::SeqCst), 2);
+        assert_eq!(memoizing.cache().lock().unwrap().len(), 2);
+    }
+
+    /// Stores a `Vec` of `len` bytes under its own name, keeping it alive
+    struct AllocatingEvent {
+        name: &'static str,
+        len: usize,
+    }
+
+    impl ChainableEvent for AllocatingEvent {
+        fn execute(&self, context: &mut EventContext) -> EventResult<()> {
+            context.set(self.name, vec![0u8; self.len]);
+            EventResult::Success(())
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    #[test]
+    fn memory_profiling_charges_each_event_its_own_allocation() {
+        let _lock = bench_lock();
+        // Keeps other test threads' allocations out of the counters
+        let _guard = BenchmarkGuard::acquire();
+        // More freed than allocated since the reset, as after a benchmark
+        MemoryStats::reset();
+        drop(std::hint::black_box(Vec::<u8>::with_capacity(4096)));
+        MemoryStats::reset();
+        let preallocated = std::hint::black_box(vec![0u8; 8192]);
+        MemoryStats::reset();
+        drop(preallocated);
+
+        let profiling = MemoryProfilingMiddleware::new();
+        let deltas = profiling.deltas();
+
+        let mut chain = EventChain::new();
+        chain.use_middleware(Box::new(profiling));
+        chain.add_event(Box::new(AllocatingEvent { name: "Small", len: 1024 }));
+        chain.add_event(Box::new(AllocatingEvent { name: "Large", len: 64 * 1024 }));
+        let mut context = EventContext::new();
+        assert!(chain.execute(&mut context).success);
+
+        let deltas = deltas.lock().unwrap();
+        assert!(deltas["Small"] >= 1024, "{}", deltas["Small"]);
+        assert!(deltas["Large"] >= 64 * 1024, "{}", deltas["Large"]);
+        // The small event is not charged for the large one's buffer
+        assert!(deltas["Small"] < 64 * 1024, "{}", deltas["Small"]);
+    }
+
+    #[test]
+    fn memory_budget_fails_an_event_allocating_past_it() {
+        let _lock = bench_lock();
+        // Keeps other test threads' allocations out of the counters
+        let _guard = BenchmarkGuard::acquire();
+        let budget = MemoryBudgetMiddleware::new(512 * 1024);
+        let run = |event: &AllocatingEvent| {
+            budget.execute(event, &mut EventContext::new(), &mut |ctx| event.execute(ctx))
+        };
+
+        let over = run(&AllocatingEvent { name: "Large", len: 1024 * 1024 });
+        assert!(matches!(over, EventResult::Failure(msg) if msg == "memory budget exceeded"));
+        assert!(run(&AllocatingEvent { name: "Small", len: 1024 }).is_success());
+    }
+
+    /// Logs "{label}:{hook}:{event}" with the calling thread's id
+    struct LoggingObserver {
+        label: &'static str,
+        log: Arc<Mutex<Vec<(String, std::thread::ThreadId)>>>,
+    }
+
+    impl LoggingObserver {
+        fn record(&self, hook: &str, event_name: &str) {
+            let entry = format!("{}:{}:{}", self.label, hook, event_name);
+            self.log.lock().unwrap().push((entry, std::thread::current().id()));
+        }
+    }
+
+    impl Observer for LoggingObserver {
+        fn before(&self, event_name: &str, context: &EventContext) {
+            // The event has not stored its buffer yet
+            assert!(!context.has(event_name));
+            self.record("before", event_name);
+        }
+
+        fn after(&self, event_name: &str, context: &EventContext, result: &EventResult<()>) {
+            assert!(context.has(event_name) && result.is_success());
+            self.record("after", event_name);
+        }
+    }
+
+    #[test]
+    fn every_observer_hook_fires_around_each_event() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let observers: Vec<Box<dyn Observer>> = ["A", "B", "C"]
+            .into_iter()
+            .map(|label| Box::new(LoggingObserver { label, log: log.clone() }) as Box<dyn Observer>)
+            .collect();
+
+        let mut chain = EventChain::new();
+        chain.use_middleware(Box::new(ParallelObserverMiddleware::new(observers)));
+        chain.add_event(Box::new(AllocatingEvent { name: "First", len: 16 }));
+        chain.add_event(Box::new(AllocatingEvent { name: "Second", len: 16 }));
+        assert!(chain.execute(&mut EventContext::new()).success);
+
+        let log = log.lock().unwrap();
+        let mut entries: Vec<&str> = log.iter().map(|(entry, _)| entry.as_str()).collect();
+        // All before hooks of an event finish before any of its after hooks
+        assert!(entries[..3].iter().all(|entry| entry.contains(":before:First")));
+        entries.sort_unstable();
+        let mut expected: Vec<String> = ["A", "B", "C"]
+            .iter()
+            .flat_map(|label| {
+                ["before:First", "after:First", "before:Second", "after:Second"]
+                    .iter()
+                    .map(move |hook| format!("{}:{}", label, hook))
+            })
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(entries, expected);
+
+        let threads: std::collections::HashSet<_> = log.iter().map(|(_, thread)| *thread).collect();
+        assert!(threads.len() > 1, "hooks all ran on one thread");
+    }
+
+    #[test]
+    fn each_observer_keeps_its_worker_across_events() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let observers: Vec<Box<dyn Observer>> = ["A", "B", "C"]
+            .into_iter()
+            .map(|label| Box::new(LoggingObserver { label, log: log.clone() }) as Box<dyn Observer>)
+            .collect();
+
+        let mut chain = EventChain::new();
+        chain.use_middleware(Box::new(ParallelObserverMiddleware::new(observers)));
+        for name in ["First", "Second", "Third"] {
+            chain.add_event(Box::new(AllocatingEvent { name, len: 16 }));
+        }
+        assert!(chain.execute(&mut EventContext::new()).success);
+
+        let log = log.lock().unwrap();
+        let threads_of = |label: &str| -> std::collections::HashSet<_> {
+            log.iter()
+                .filter(|(entry, _)| entry.starts_with(label))
+                .map(|(_, thread)| *thread)
+                .collect()
+        };
+        let caller = std::thread::current().id();
+        assert_eq!(threads_of("A"), [caller].into());
+        let (b, c) = (threads_of("B"), threads_of("C"));
+        assert_eq!((b.len(), c.len()), (1, 1));
+        assert!(!b.contains(&caller) && b != c);
+    }
+
+    struct PanickingObserver;
+
+    impl Observer for PanickingObserver {
+        fn before(&self, _event_name: &str, _context: &EventContext) {
+            panic!("observer failed");
+        }
+
+        fn after(&self, _event_name: &str, _context: &EventContext, _result: &EventResult<()>) {}
+    }
+
+    #[test]
+    fn a_panicking_worker_hook_reaches_the_caller() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let observers: Vec<Box<dyn Observer>> =
+            vec![Box::new(LoggingObserver { label: "A", log: log.clone() }), Box::new(PanickingObserver)];
+        let middleware = ParallelObserverMiddleware::new(observers);
+        let event = AllocatingEvent { name: "First", len: 16 };
+
+        let run = || middleware.execute(&event, &mut EventContext::new(), &mut |ctx| event.execute(ctx));
+        let payload = std::panic::catch_unwind(AssertUnwindSafe(run)).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"observer failed"));
+        // The other observer's hook still ran, and the worker survives
+        assert_eq!(log.lock().unwrap()[0].0, "A:before:First");
+        assert!(std::panic::catch_unwind(AssertUnwindSafe(run)).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::eventchains::{ChainableEvent, EventContext, EventMiddleware, EventResult};
+use crate::middleware::Observer;
 
 /// No-op middleware for measuring overhead
 pub struct NoOpMiddleware {
@@ -39,6 +40,7 @@ impl CountingMiddleware {
         }
     }
     
+    #[allow(dead_code, reason = "the count is for validation; benchmarks only measure the middleware's cost")]
     pub fn get_count(&self) -> u64 {
         *self.counter.lock().unwrap()
     }
@@ -64,3 +66,143 @@ impl Default for CountingMiddleware {
         Self::new()
     }
 }
+
+/// Order-recording middleware for validating LIFO dispatch: appends
+/// "{label}:before" and "{label}:after" around `next` to a shared log
+pub struct OrderRecordingMiddleware {
+    label: String,
+    log: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl OrderRecordingMiddleware {
+    pub fn new(label: &str, log: std::sync::Arc<std::sync::Mutex<Vec<String>>>) -> Self {
+        Self {
+            label: label.to_string(),
+            log,
+        }
+    }
+}
+
+impl EventMiddleware for OrderRecordingMiddleware {
+    fn execute(
+        &self,
+        _event: &dyn ChainableEvent,
+        context: &mut EventContext,
+        next: &mut dyn FnMut(&mut EventContext) -> EventResult<()>,
+    ) -> EventResult<()> {
+        self.log.lock().unwrap().push(format!("{}:before", self.label));
+        let result = next(context);
+        self.log.lock().unwrap().push(format!("{}:after", self.label));
+        result
+    }
+}
+
+/// Observer whose hooks do nothing, for measuring the dispatch cost of
+/// `ParallelObserverMiddleware` on its own
+pub struct NoOpObserver;
+
+impl Observer for NoOpObserver {
+    fn before(&self, _event_name: &str, _context: &EventContext) {}
+
+    fn after(&self, _event_name: &str, _context: &EventContext, _result: &EventResult<()>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eventchains::{DispatchStrategy, EventChain, FaultToleranceMode, MiddlewarePriority};
+    use std::sync::{Arc, Mutex};
+
+    /// Appends "event" to the log, then fails if asked to
+    struct LoggedEvent {
+        log: Arc<Mutex<Vec<String>>>,
+        fail: bool,
+    }
+
+    impl ChainableEvent for LoggedEvent {
+        fn execute(&self, _context: &mut EventContext) -> EventResult<()> {
+            self.log.lock().unwrap().push("event".to_string());
+            if self.fail {
+                EventResult::Failure("failed".to_string())
+            } else {
+                EventResult::Success(())
+            }
+        }
+
+        fn name(&self) -> &str {
+            "Logged"
+        }
+    }
+
+    fn recorded_order(dispatch: DispatchStrategy, mode: FaultToleranceMode, fail: bool) -> Vec<String> {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut chain = EventChain::new().with_fault_tolerance(mode).with_dispatch(dispatch);
+        for label in ["A", "B", "C"] {
+            chain.use_middleware(Box::new(OrderRecordingMiddleware::new(label, log.clone())));
+        }
+        chain.add_event(Box::new(LoggedEvent {
+            log: log.clone(),
+            fail,
+        }));
+
+        chain.execute(&mut EventContext::new());
+        let order = log.lock().unwrap().clone();
+        order
+    }
+
+    #[test]
+    fn last_registered_middleware_runs_outermost() {
+        let expected = [
+            "C:before", "B:before", "A:before", "event", "A:after", "B:after", "C:after",
+        ];
+        for dispatch in [DispatchStrategy::Recursive, DispatchStrategy::Iterative] {
+            for mode in [FaultToleranceMode::Strict, FaultToleranceMode::Lenient, FaultToleranceMode::BestEffort] {
+                for fail in [false, true] {
+                    assert_eq!(
+                        recorded_order(dispatch, mode, fail),
+                        expected,
+                        "{:?} {:?} failing: {}",
+                        dispatch,
+                        mode,
+                        fail
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pinned_middleware_keeps_its_place_regardless_of_registration_order() {
+        for dispatch in [DispatchStrategy::Recursive, DispatchStrategy::Iterative] {
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let recorder = |label| Box::new(OrderRecordingMiddleware::new(label, log.clone()));
+            let mut chain = EventChain::new().with_dispatch(dispatch);
+            chain.use_middleware(recorder("A"));
+            chain.use_middleware_with_priority(recorder("Log"), MiddlewarePriority::Outermost);
+            chain.use_middleware(recorder("B"));
+            chain.use_middleware(recorder("C"));
+            chain.use_middleware_with_priority(recorder("I"), MiddlewarePriority::Innermost);
+            // Clamped to just outside the pinned innermost middleware
+            chain.insert_middleware(0, recorder("X"));
+            chain.add_event(Box::new(LoggedEvent {
+                log: log.clone(),
+                fail: false,
+            }));
+
+            chain.execute(&mut EventContext::new());
+            let befores: Vec<String> = log
+                .lock()
+                .unwrap()
+                .iter()
+                .take_while(|entry| *entry != "event")
+                .cloned()
+                .collect();
+            assert_eq!(
+                befores,
+                ["Log:before", "C:before", "B:before", "A:before", "X:before", "I:before"],
+                "{:?}",
+                dispatch
+            );
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use crate::graph::{DijkstraState, Graph, NodeId, ShortestPathResult};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+// All queues here break distance ties the same way `QueueNode` does in a
+// `BinaryHeap` (higher node id first), so every variant settles nodes in
+// the same order and produces identical results.
+
+/// Binary min-heap over node ids with a position index, supporting
+/// decrease-key instead of pushing duplicate entries
+pub struct IndexedMinHeap {
+    heap: Vec<NodeId>,
+    // Index of each node in `heap`, or usize::MAX if absent
+    positions: Vec<usize>,
+    keys: Vec<u32>,
+}
+
+impl IndexedMinHeap {
+    pub fn new(nodes: usize) -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: vec![usize::MAX; nodes],
+            keys: vec![u32::MAX; nodes],
+        }
+    }
+
+    #[allow(dead_code, reason = "the search loop pops until `None`; only tests check emptiness")]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, node: NodeId) -> bool {
+        self.positions[node.0] != usize::MAX
+    }
+
+    /// Insert `node` with `key`, or lower its key if already present.
+    /// A key higher than the current one is ignored.
+    pub fn push_or_decrease(&mut self, node: NodeId, key: u32) {
+        if self.contains(node) {
+            if key < self.keys[node.0] {
+                self.keys[node.0] = key;
+                self.sift_up(self.positions[node.0]);
+            }
+        } else {
+            self.keys[node.0] = key;
+            self.positions[node.0] = self.heap.len();
+            self.heap.push(node);
+            self.sift_up(self.heap.len() - 1);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<(NodeId, u32)> {
+        let top = *self.heap.first()?;
+        let last = self.heap.pop().unwrap();
+        self.positions[top.0] = usize::MAX;
+
+        if !self.heap.is_empty() {
+            self.heap[0] = last;
+            self.positions[last.0] = 0;
+            self.sift_down(0);
+        }
+
+        Some((top, self.keys[top.0]))
+    }
+
+    fn less(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.heap[a], self.heap[b]);
+        (self.keys[a.0], Reverse(a.0)) < (self.keys[b.0], Reverse(b.0))
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a].0] = a;
+        self.positions[self.heap[b].0] = b;
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.less(index, parent) {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut smallest = index;
+
+            if left < self.heap.len() && self.less(left, smallest) {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.less(right, smallest) {
+                smallest = right;
+            }
+            if smallest == index {
+                break;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+}
+
+/// Tier 1 baseline with an ordered `BTreeSet` as the priority queue;
+/// relaxed nodes are re-keyed by removing their old entry
+pub fn dijkstra_btreeset_baseline(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let mut state = DijkstraState::new(graph.nodes, source);
+
+    let mut queue = BTreeSet::new();
+    queue.insert((0u32, Reverse(source.0)));
+
+    while let Some((distance, Reverse(index))) = queue.pop_first() {
+        let node = NodeId(index);
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        for edge in &graph.adjacency_list[node.0] {
+            if state.visited[edge.to.0] {
+                continue;
+            }
+
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                queue.remove(&(state.distances[edge.to.0], Reverse(edge.to.0)));
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+                queue.insert((new_distance, Reverse(edge.to.0)));
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    ShortestPathResult::reconstruct_path(&state, source, target)
+}
+
+/// Tier 1 baseline with an `IndexedMinHeap`, so each node is queued at most
+/// once and relaxations decrease its key in place
+pub fn dijkstra_indexed_heap_baseline(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let mut state = DijkstraState::new(graph.nodes, source);
+
+    let mut queue = IndexedMinHeap::new(graph.nodes);
+    queue.push_or_decrease(source, 0);
+
+    while let Some((node, distance)) = queue.pop() {
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        for edge in &graph.adjacency_list[node.0] {
+            if state.visited[edge.to.0] {
+                continue;
+            }
+
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+                queue.push_or_decrease(edge.to, new_distance);
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    ShortestPathResult::reconstruct_path(&state, source, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tier_baselines::dijkstra_tier1_baseline;
+
+    #[test]
+    fn every_queue_produces_the_same_result() {
+        for seed in 0..10 {
+            let graph = Arc::new(Graph::random_connected_seeded(80, 300, 20, seed));
+            let (source, target) = (NodeId(seed as usize), NodeId(79));
+
+            let binary_heap = dijkstra_tier1_baseline(graph.clone(), source, target);
+            let others = [
+                ("BTreeSet", dijkstra_btreeset_baseline(graph.clone(), source, target)),
+                ("indexed heap", dijkstra_indexed_heap_baseline(graph.clone(), source, target)),
+            ];
+            for (label, result) in others {
+                assert_eq!(result.distance, binary_heap.distance, "{} seed {}", label, seed);
+                assert_eq!(result.path, binary_heap.path, "{} seed {}", label, seed);
+                assert_eq!(result.nodes_expanded, binary_heap.nodes_expanded, "{} seed {}", label, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn indexed_heap_decreases_keys_in_place() {
+        let mut heap = IndexedMinHeap::new(4);
+        heap.push_or_decrease(NodeId(1), 10);
+        heap.push_or_decrease(NodeId(2), 5);
+        heap.push_or_decrease(NodeId(1), 3);
+        heap.push_or_decrease(NodeId(2), 8);
+
+        assert_eq!(heap.pop(), Some((NodeId(1), 3)));
+        assert_eq!(heap.pop(), Some((NodeId(2), 5)));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
-use crate::graph::{DijkstraState, Graph, NodeId, QueueNode, ShortestPathResult};
-use std::collections::BinaryHeap;
+use crate::eventchains::EventResult;
+use crate::graph::{CsrGraph, DijkstraState, FrozenGraph, Graph, NodeId, QueueNode, ShortestPathResult};
+use std::collections::{BinaryHeap, VecDeque};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -30,6 +31,7 @@ pub fn dijkstra_tier1_baseline(
         }
 
         state.visited[node.0] = true;
+        state.nodes_expanded += 1;
 
         if node == target {
             break;  // ✅ EARLY EXIT OPTIMIZATION
@@ -46,6 +48,8 @@ pub fn dijkstra_tier1_baseline(
                     node: edge.to,
                     distance: new_distance,
                 });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
             }
         }
     }
@@ -54,6 +58,185 @@ pub fn dijkstra_tier1_baseline(
     ShortestPathResult::reconstruct_path(&state, source, target)
 }
 
+/// Tier 1 baseline for callers that only need the path cost: no
+/// predecessor vector is allocated and no path is reconstructed
+pub fn dijkstra_distance_only_baseline(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> Option<u32> {
+    let mut state = DijkstraState::distance_only(graph.nodes, source);
+
+    let mut queue = BinaryHeap::new();
+    queue.push(QueueNode {
+        node: source,
+        distance: 0,
+    });
+
+    while let Some(QueueNode { node, distance }) = queue.pop() {
+        if state.visited[node.0] || distance > state.distances[node.0] {
+            continue;
+        }
+
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        for edge in &graph.adjacency_list[node.0] {
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+
+                queue.push(QueueNode {
+                    node: edge.to,
+                    distance: new_distance,
+                });
+            }
+        }
+    }
+
+    ShortestPathResult::distance_only(&state, target)
+}
+
+/// Tier 1 baseline with every step's outcome wrapped in `EventResult<()>`
+/// and checked before the next step runs, as a Strict chain does, but with
+/// no events, context, or dynamic dispatch. Its gap to the bare baseline is
+/// the cost of the result enum alone.
+pub fn dijkstra_result_wrapped_baseline(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let mut state = DijkstraState::new(graph.nodes, source);
+    let mut queue = BinaryHeap::new();
+
+    let outcome = step_outcome(EventResult::Success(()))
+        .and_then(|()| {
+            queue.push(QueueNode {
+                node: source,
+                distance: 0,
+            });
+            step_outcome(EventResult::Success(()))
+        })
+        .and_then(|()| step_outcome(process_nodes_until(&graph, &mut state, &mut queue, target)));
+
+    match outcome {
+        EventResult::Success(()) => ShortestPathResult::reconstruct_path(&state, source, target),
+        _ => ShortestPathResult::unreachable(source, target),
+    }
+}
+
+/// Tier 1 baseline that flags path-cost overflow instead of hiding it. The
+/// other searches relax with `saturating_add`, so a path costing more than
+/// `u32::MAX` is capped, never improves a distance, and its target looks
+/// unreachable. Here such relaxations are still dropped, but the result has
+/// `overflowed` set.
+///
+/// Weights are `u32` throughout the crate and there is no wider weight type,
+/// so graphs whose path costs can approach `u32::MAX` should have their
+/// weights scaled down before searching.
+pub fn dijkstra_overflow_checked(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let mut state = DijkstraState::new(graph.nodes, source);
+    let mut overflowed = false;
+
+    let mut queue = BinaryHeap::new();
+    queue.push(QueueNode {
+        node: source,
+        distance: 0,
+    });
+
+    while let Some(QueueNode { node, distance }) = queue.pop() {
+        if state.visited[node.0] || distance > state.distances[node.0] {
+            continue;
+        }
+
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        for edge in &graph.adjacency_list[node.0] {
+            // u32::MAX itself marks "unreached", so it overflows too
+            let new_distance = match distance.checked_add(edge.weight) {
+                Some(sum) if sum != u32::MAX => sum,
+                _ => {
+                    overflowed = true;
+                    continue;
+                }
+            };
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+
+                queue.push(QueueNode {
+                    node: edge.to,
+                    distance: new_distance,
+                });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    let mut result = ShortestPathResult::reconstruct_path(&state, source, target);
+    result.overflowed = overflowed;
+    result
+}
+
+/// Hide a step's outcome from the optimizer, which could otherwise fold the
+/// checks away; a boxed event's result is just as opaque
+fn step_outcome(result: EventResult<()>) -> EventResult<()> {
+    std::hint::black_box(result)
+}
+
+fn process_nodes_until(
+    graph: &Graph,
+    state: &mut DijkstraState,
+    queue: &mut BinaryHeap<QueueNode>,
+    target: NodeId,
+) -> EventResult<()> {
+    while let Some(QueueNode { node, distance }) = queue.pop() {
+        if state.visited[node.0] || distance > state.distances[node.0] {
+            continue;
+        }
+
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        for edge in &graph.adjacency_list[node.0] {
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+
+                queue.push(QueueNode {
+                    node: edge.to,
+                    distance: new_distance,
+                });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+    EventResult::Success(())
+}
+
 // ============================================================================
 // TIER 2 BASELINE: Manual Instrumented (Feature-Parity)
 // ============================================================================
@@ -111,6 +294,7 @@ fn process_nodes_with_validation(
         }
 
         state.visited[node.0] = true;
+        state.nodes_expanded += 1;
 
         for edge in &graph.adjacency_list[node.0] {
             let new_distance = distance.saturating_add(edge.weight);
@@ -123,6 +307,8 @@ fn process_nodes_with_validation(
                     node: edge.to,
                     distance: new_distance,
                 });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
             }
         }
     }
@@ -208,6 +394,7 @@ pub fn dijkstra_tier4_baseline(
         }
 
         state.visited[node.0] = true;
+        state.nodes_expanded += 1;
 
         for edge in &graph.adjacency_list[node.0] {
             let new_distance = distance.saturating_add(edge.weight);
@@ -220,6 +407,8 @@ pub fn dijkstra_tier4_baseline(
                     node: edge.to,
                     distance: new_distance,
                 });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
             }
         }
     }
@@ -252,3 +441,599 @@ impl Default for ManualLoggingContext {
         Self::new()
     }
 }
+
+// ============================================================================
+// LAYOUT BASELINE: CSR Adjacency
+// ============================================================================
+
+/// Tier 1 baseline over a `CsrGraph`; results are identical to
+/// `dijkstra_tier1_baseline` on the graph it was built from
+pub fn dijkstra_csr_baseline(
+    graph: Arc<CsrGraph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    dijkstra_over_csr(&graph, source, target)
+}
+
+/// Tier 1 baseline over a `FrozenGraph`, with the same results and
+/// exploration as `dijkstra_tier1_baseline` on the graph that was frozen
+pub fn dijkstra_frozen_baseline(
+    graph: Arc<FrozenGraph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    dijkstra_over_csr(graph.as_csr(), source, target)
+}
+
+fn dijkstra_over_csr(graph: &CsrGraph, source: NodeId, target: NodeId) -> ShortestPathResult {
+    let mut state = DijkstraState::new(graph.nodes, source);
+
+    let mut queue = BinaryHeap::new();
+    queue.push(QueueNode {
+        node: source,
+        distance: 0,
+    });
+
+    while let Some(QueueNode { node, distance }) = queue.pop() {
+        if state.visited[node.0] || distance > state.distances[node.0] {
+            continue;
+        }
+
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        for edge in graph.neighbors(node) {
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+
+                queue.push(QueueNode {
+                    node: edge.to,
+                    distance: new_distance,
+                });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    ShortestPathResult::reconstruct_path(&state, source, target)
+}
+
+// ============================================================================
+// BFS BASELINE: Hop Counts on Unit-Weight Graphs
+// ============================================================================
+
+/// Breadth-first search from `source` to `target` with a FIFO frontier.
+/// Edge weights are ignored: the distance is the number of hops, which
+/// equals the Dijkstra distance only when every weight is 1. On such
+/// graphs it is the algorithmic floor for the other baselines, and ties
+/// are broken like Dijkstra's so the paths match too.
+pub fn bfs_shortest_path_baseline(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let mut state = DijkstraState::new(graph.nodes, source);
+
+    let mut frontier = VecDeque::new();
+    frontier.push_back(source);
+
+    while let Some(node) = frontier.pop_front() {
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        let next_distance = state.distances[node.0] + 1;
+        for edge in &graph.adjacency_list[node.0] {
+            if state.distances[edge.to.0] == u32::MAX {
+                state.distances[edge.to.0] = next_distance;
+                state.predecessors[edge.to.0] = Some(node);
+                frontier.push_back(edge.to);
+            } else if state.distances[edge.to.0] == next_distance {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    ShortestPathResult::reconstruct_path(&state, source, target)
+}
+
+// ============================================================================
+// MULTI-SOURCE BASELINE: Distance From the Nearest of Several Sources
+// ============================================================================
+
+/// Tier 1 baseline with every node in `sources` seeded at distance 0. The
+/// result's `reached_from` is the source nearest to `target`.
+pub fn dijkstra_multi_source_baseline(
+    graph: Arc<Graph>,
+    sources: &[NodeId],
+    target: NodeId,
+) -> ShortestPathResult {
+    assert!(!sources.is_empty(), "multi-source search needs at least one source");
+
+    let mut state = DijkstraState::new_multi_source(graph.nodes, sources);
+
+    let mut queue = BinaryHeap::new();
+    for &source in sources {
+        queue.push(QueueNode {
+            node: source,
+            distance: 0,
+        });
+    }
+
+    while let Some(QueueNode { node, distance }) = queue.pop() {
+        if state.visited[node.0] || distance > state.distances[node.0] {
+            continue;
+        }
+
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if node == target {
+            break;
+        }
+
+        for edge in &graph.adjacency_list[node.0] {
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+
+                queue.push(QueueNode {
+                    node: edge.to,
+                    distance: new_distance,
+                });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    ShortestPathResult::reconstruct_multi_source_path(&state, sources, target)
+}
+
+// ============================================================================
+// BIDIRECTIONAL BASELINE: Forward and Backward Search Meeting in the Middle
+// ============================================================================
+
+/// Point-to-point Dijkstra searching forward from `source` over `graph` and
+/// backward from `target` over `graph.reverse()`, always advancing the side
+/// with the smaller frontier key. Stops once the two frontier keys sum to
+/// more than the best meeting distance found. Builds the reverse graph on
+/// every call; `nodes_expanded` counts both directions.
+///
+/// Returns the same canonical path as `dijkstra_tier1_baseline`: settled
+/// nodes know their smallest forward prefix and backward suffix, and every
+/// shortest path crosses between the two settled regions, so the smallest
+/// join across a tight edge is the lexicographically smallest path.
+pub fn dijkstra_bidirectional_baseline(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+) -> ShortestPathResult {
+    let reverse = graph.reverse();
+    let mut forward = DijkstraState::new(graph.nodes, source);
+    let mut backward = DijkstraState::new(graph.nodes, target);
+
+    let mut forward_queue = BinaryHeap::new();
+    forward_queue.push(QueueNode {
+        node: source,
+        distance: 0,
+    });
+    let mut backward_queue = BinaryHeap::new();
+    backward_queue.push(QueueNode {
+        node: target,
+        distance: 0,
+    });
+
+    // Best known source -> meet -> target distance
+    let mut best = if source == target { 0 } else { u32::MAX };
+    let mut meet = if source == target { Some(source) } else { None };
+
+    while let (Some(f), Some(b)) = (forward_queue.peek(), backward_queue.peek()) {
+        let (forward_top, backward_top) = (f.distance, b.distance);
+        // Strictly past `best`, so every node at the boundary is settled on
+        // one side or the other
+        if forward_top.saturating_add(backward_top) > best {
+            break;
+        }
+
+        let is_forward = forward_top <= backward_top;
+        let (queue, state, other, adjacency) = if is_forward {
+            (&mut forward_queue, &mut forward, &backward, &graph.adjacency_list)
+        } else {
+            (&mut backward_queue, &mut backward, &forward, &reverse.adjacency_list)
+        };
+
+        let QueueNode { node, distance } = queue.pop().unwrap();
+        if state.visited[node.0] || distance > state.distances[node.0] {
+            continue;
+        }
+
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        for edge in &adjacency[node.0] {
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+
+                queue.push(QueueNode {
+                    node: edge.to,
+                    distance: new_distance,
+                });
+            } else if new_distance == state.distances[edge.to.0] {
+                if is_forward {
+                    state.break_tie(node, edge.to);
+                } else if !state.visited[edge.to.0]
+                    && state.predecessors[edge.to.0].is_some_and(|next| node.0 < next.0)
+                {
+                    // A backward predecessor is the next hop toward the target,
+                    // so the smallest one starts the smallest suffix
+                    state.predecessors[edge.to.0] = Some(node);
+                }
+            }
+
+            let through = state.distances[edge.to.0].saturating_add(other.distances[edge.to.0]);
+            if through < best {
+                best = through;
+                meet = Some(edge.to);
+            }
+        }
+    }
+
+    // Join the prefix ending at `last` with the suffix starting at `next`
+    let join = |last: NodeId, next: Option<NodeId>| {
+        let mut path = Vec::new();
+        let mut current = Some(last);
+        while let Some(node) = current {
+            path.push(node);
+            current = forward.predecessors[node.0];
+        }
+        path.reverse();
+
+        let mut current = next;
+        while let Some(node) = current {
+            path.push(node);
+            current = backward.predecessors[node.0];
+        }
+        path
+    };
+    let forward_final = |node: NodeId| node == source || forward.visited[node.0];
+    let backward_final = |node: NodeId| node == target || backward.visited[node.0];
+
+    let mut path: Vec<NodeId> = Vec::new();
+    if meet.is_some() {
+        let mut consider = |candidate: Vec<NodeId>| {
+            if path.is_empty() || candidate.iter().map(|node| node.0).lt(path.iter().map(|node| node.0)) {
+                path = candidate;
+            }
+        };
+        for last in (0..graph.nodes).map(NodeId).filter(|&node| forward_final(node)) {
+            let to_last = forward.distances[last.0];
+            if backward_final(last) && to_last.saturating_add(backward.distances[last.0]) == best {
+                consider(join(last, backward.predecessors[last.0]));
+            }
+            for edge in &graph.adjacency_list[last.0] {
+                let through = to_last.saturating_add(edge.weight).saturating_add(backward.distances[edge.to.0]);
+                if backward_final(edge.to) && through == best {
+                    consider(join(last, Some(edge.to)));
+                }
+            }
+        }
+    }
+
+    ShortestPathResult {
+        source,
+        target,
+        distance: meet.map(|_| best),
+        path,
+        reached_from: None,
+        nodes_expanded: forward.nodes_expanded + backward.nodes_expanded,
+        overflowed: false,
+    }
+}
+
+// ============================================================================
+// MAX-FLOW BASELINE: Edmonds-Karp on the Same Graphs
+// ============================================================================
+
+/// Maximum flow from `source` to `target` by Edmonds-Karp: repeatedly
+/// augment along the shortest (fewest-hop) residual path found by BFS.
+///
+/// Every adjacency entry is a directed arc whose capacity is its weight, so
+/// a bidirectional edge contributes capacity in both directions and
+/// parallel edges add up. Gives a non-shortest-path workload over the same
+/// `Graph` for comparing orchestration overhead across algorithm families.
+#[allow(dead_code, reason = "a flow workload for ad-hoc comparisons, too slow to run for every test case")]
+pub fn max_flow_baseline(graph: Arc<Graph>, source: NodeId, target: NodeId) -> u32 {
+    if source == target {
+        return 0;
+    }
+
+    // Residual arcs in pairs: arc `i ^ 1` is the reverse of arc `i`
+    let mut heads = Vec::new();
+    let mut capacities = Vec::new();
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); graph.nodes];
+    for (from, edges) in graph.adjacency_list.iter().enumerate() {
+        for edge in edges {
+            outgoing[from].push(heads.len());
+            heads.push(edge.to.0);
+            capacities.push(edge.weight);
+
+            outgoing[edge.to.0].push(heads.len());
+            heads.push(from);
+            capacities.push(0);
+        }
+    }
+
+    let mut flow = 0u32;
+    let mut via_arc: Vec<Option<usize>> = vec![None; graph.nodes];
+    let mut frontier = VecDeque::new();
+
+    loop {
+        via_arc.fill(None);
+        frontier.clear();
+        frontier.push_back(source.0);
+
+        while let Some(node) = frontier.pop_front() {
+            if node == target.0 {
+                break;
+            }
+            for &arc in &outgoing[node] {
+                let next = heads[arc];
+                if capacities[arc] > 0 && next != source.0 && via_arc[next].is_none() {
+                    via_arc[next] = Some(arc);
+                    frontier.push_back(next);
+                }
+            }
+        }
+
+        if via_arc[target.0].is_none() {
+            return flow;
+        }
+
+        let mut bottleneck = u32::MAX;
+        let mut node = target.0;
+        while let Some(arc) = via_arc[node] {
+            bottleneck = bottleneck.min(capacities[arc]);
+            node = heads[arc ^ 1];
+        }
+
+        let mut node = target.0;
+        while let Some(arc) = via_arc[node] {
+            capacities[arc] -= bottleneck;
+            capacities[arc ^ 1] += bottleneck;
+            node = heads[arc ^ 1];
+        }
+
+        flow = flow.saturating_add(bottleneck);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Edge;
+
+    #[test]
+    fn multi_source_search_reports_the_nearest_source() {
+        // 0 -10-> 2 <-3- 1
+        let mut graph = Graph::new(3);
+        graph.add_edge(NodeId(0), NodeId(2), 10);
+        graph.add_edge(NodeId(1), NodeId(2), 3);
+
+        let result = dijkstra_multi_source_baseline(Arc::new(graph), &[NodeId(0), NodeId(1)], NodeId(2));
+
+        assert_eq!(result.distance, Some(3));
+        assert_eq!(result.reached_from, Some(NodeId(1)));
+        assert_eq!(result.path, vec![NodeId(1), NodeId(2)]);
+    }
+
+    #[test]
+    fn unreachable_target_matches_the_unreachable_result() {
+        let graph = Arc::new(Graph::new(2));
+        let result = dijkstra_tier1_baseline(graph, NodeId(0), NodeId(1));
+        let expected = ShortestPathResult::unreachable(NodeId(0), NodeId(1));
+
+        assert_eq!(result.distance, expected.distance);
+        assert_eq!(result.path, expected.path);
+        assert_eq!(result.reached_from, None);
+    }
+
+    #[test]
+    fn csr_search_matches_the_adjacency_list_search() {
+        for seed in 0..5 {
+            let graph = Graph::random_connected_seeded(80, 300, 100, seed);
+            let csr = Arc::new(CsrGraph::from(&graph));
+            let pairs = |edges: &[Edge]| edges.iter().map(|e| (e.to, e.weight)).collect::<Vec<_>>();
+            for node in 0..graph.nodes {
+                assert_eq!(pairs(csr.neighbors(NodeId(node))), pairs(&graph.adjacency_list[node]));
+            }
+
+            let graph = Arc::new(graph);
+            for target in [1, 40, 79] {
+                let expected = dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(target));
+                let result = dijkstra_csr_baseline(csr.clone(), NodeId(0), NodeId(target));
+                assert_eq!(result.distance, expected.distance, "seed {} target {}", seed, target);
+                assert_eq!(result.path, expected.path, "seed {} target {}", seed, target);
+            }
+        }
+    }
+
+    #[test]
+    fn bidirectional_search_matches_and_expands_fewer_nodes() {
+        let graph = Arc::new(Graph::random_connected_seeded(2000, 6000, 100, 4));
+        let (mut unidirectional, mut bidirectional) = (0, 0);
+
+        for target in (100..2000).step_by(100) {
+            let (source, target) = (NodeId(0), NodeId(target));
+            let expected = dijkstra_tier1_baseline(graph.clone(), source, target);
+            let result = dijkstra_bidirectional_baseline(graph.clone(), source, target);
+
+            assert_eq!(result.distance, expected.distance, "target {}", target.0);
+            result.verify(&graph).unwrap();
+            unidirectional += expected.nodes_expanded;
+            bidirectional += result.nodes_expanded;
+        }
+        assert!(bidirectional < unidirectional, "{} vs {}", bidirectional, unidirectional);
+    }
+
+    #[test]
+    fn bidirectional_search_returns_the_canonical_path() {
+        // Weights of 1..=3 leave many equal-cost paths to choose between
+        for seed in 0..20 {
+            let graph = Arc::new(Graph::random_connected_seeded(60, 200, 3, seed));
+            for target in (0..60).step_by(7) {
+                let (source, target) = (NodeId(seed as usize % 60), NodeId(target));
+                let expected = dijkstra_tier1_baseline(graph.clone(), source, target);
+                let result = dijkstra_bidirectional_baseline(graph.clone(), source, target);
+                assert_eq!(result.path, expected.path, "seed {} target {}", seed, target.0);
+            }
+        }
+    }
+
+    #[test]
+    fn bidirectional_search_follows_directed_edges() {
+        // 0 -> 1 -> 2, with only a reverse shortcut 2 -> 0
+        let mut graph = Graph::new(3);
+        graph.add_edge(NodeId(0), NodeId(1), 4);
+        graph.add_edge(NodeId(1), NodeId(2), 4);
+        graph.add_edge(NodeId(2), NodeId(0), 1);
+        let graph = Arc::new(graph);
+
+        let result = dijkstra_bidirectional_baseline(graph.clone(), NodeId(0), NodeId(2));
+        assert_eq!(result.distance, Some(8));
+        assert_eq!(result.path, [NodeId(0), NodeId(1), NodeId(2)]);
+        assert_eq!(dijkstra_bidirectional_baseline(graph, NodeId(2), NodeId(1)).distance, Some(5));
+    }
+
+    #[test]
+    fn distance_only_search_matches_without_allocating_predecessors() {
+        use crate::comprehensive_benchmarking::{bench_lock, run_comprehensive_benchmark};
+        let _lock = bench_lock();
+
+        let graph = Arc::new(Graph::random_connected_seeded(1_000, 4_000, 100, 5));
+        for target in [0, 1, 250, 999] {
+            let full = dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(target));
+            let distance = dijkstra_distance_only_baseline(graph.clone(), NodeId(0), NodeId(target));
+            assert_eq!(distance, full.distance, "target {}", target);
+        }
+
+        let bytes_per_query = |distance_only: bool| {
+            run_comprehensive_benchmark(10, || {
+                if distance_only {
+                    std::hint::black_box(dijkstra_distance_only_baseline(graph.clone(), NodeId(0), NodeId(999)));
+                } else {
+                    std::hint::black_box(dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(999)));
+                }
+                Ok(())
+            })
+            .bytes_allocated_per_run
+        };
+        // Everything else is shared, so the gap is at least the
+        // `Option<NodeId>` per node the full search records predecessors in
+        let saved = bytes_per_query(false) - bytes_per_query(true);
+        let predecessors = (1_000 * std::mem::size_of::<Option<NodeId>>()) as f64;
+        assert!(saved >= predecessors, "{} < {}", saved, predecessors);
+    }
+
+    #[test]
+    fn bfs_matches_dijkstra_on_a_unit_weight_grid() {
+        let graph = Arc::new(Graph::grid(12, 9, 1..=1));
+        for target in [0, 1, 50, 107] {
+            let bfs = bfs_shortest_path_baseline(graph.clone(), NodeId(3), NodeId(target));
+            let dijkstra = dijkstra_tier1_baseline(graph.clone(), NodeId(3), NodeId(target));
+            assert_eq!(bfs.distance, dijkstra.distance, "target {}", target);
+            assert_eq!(bfs.path, dijkstra.path, "target {}", target);
+        }
+    }
+
+    #[test]
+    fn max_flow_of_the_classic_six_node_network() {
+        // The flow network from CLRS, figure 26.1: source 0, sink 5
+        let mut graph = Graph::new(6);
+        for (from, to, capacity) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ] {
+            graph.add_edge(NodeId(from), NodeId(to), capacity);
+        }
+        let graph = Arc::new(graph);
+
+        assert_eq!(max_flow_baseline(graph.clone(), NodeId(0), NodeId(5)), 23);
+        // Nothing flows back out of the sink
+        assert_eq!(max_flow_baseline(graph.clone(), NodeId(5), NodeId(0)), 0);
+        assert_eq!(max_flow_baseline(graph, NodeId(0), NodeId(0)), 0);
+    }
+
+    #[test]
+    fn result_wrapping_leaves_distances_unchanged() {
+        let graph = Arc::new(Graph::random_connected_seeded(500, 2_500, 100, 13));
+        for target in [0, 1, 123, 499] {
+            let plain = dijkstra_tier1_baseline(graph.clone(), NodeId(7), NodeId(target));
+            let wrapped = dijkstra_result_wrapped_baseline(graph.clone(), NodeId(7), NodeId(target));
+            assert_eq!(wrapped.distance, plain.distance, "target {}", target);
+            assert_eq!(wrapped.path, plain.path, "target {}", target);
+        }
+
+        let disconnected = Arc::new(Graph::new(2));
+        assert_eq!(dijkstra_result_wrapped_baseline(disconnected, NodeId(0), NodeId(1)).distance, None);
+    }
+
+    #[test]
+    fn near_max_weights_set_the_overflow_flag() {
+        // 0 -(MAX - 10)-> 1 -20-> 2: the only path to 2 costs more than u32::MAX
+        let mut graph = Graph::new(3);
+        graph.add_edge(NodeId(0), NodeId(1), u32::MAX - 10);
+        graph.add_edge(NodeId(1), NodeId(2), 20);
+        let graph = Arc::new(graph);
+
+        let result = dijkstra_overflow_checked(graph.clone(), NodeId(0), NodeId(2));
+        assert!(result.overflowed);
+        assert_eq!(result.distance, None);
+        // The saturating search cannot tell this apart from unreachable
+        let saturated = dijkstra_tier1_baseline(graph.clone(), NodeId(0), NodeId(2));
+        assert!(!saturated.overflowed);
+
+        let reachable = dijkstra_overflow_checked(graph, NodeId(0), NodeId(1));
+        assert_eq!(reachable.distance, Some(u32::MAX - 10));
+        assert!(!reachable.overflowed);
+    }
+
+    #[test]
+    fn frozen_graph_explores_exactly_like_the_mutable_one() {
+        let graph = Graph::random_connected_seeded(400, 2_000, 100, 15);
+        let frozen = Arc::new(graph.clone().freeze());
+        let graph = Arc::new(graph);
+
+        for target in [0, 3, 200, 399] {
+            let expected = dijkstra_tier1_baseline(graph.clone(), NodeId(9), NodeId(target));
+            let result = dijkstra_frozen_baseline(frozen.clone(), NodeId(9), NodeId(target));
+            assert_eq!(result.distance, expected.distance, "target {}", target);
+            assert_eq!(result.path, expected.path, "target {}", target);
+            assert_eq!(result.nodes_expanded, expected.nodes_expanded, "target {}", target);
+        }
+    }
+}
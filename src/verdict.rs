@@ -0,0 +1,117 @@
+use crate::comprehensive_benchmarking::ComprehensiveMetrics;
+use colored::*;
+
+/// Pass/fail outcome of one tier in one test case
+#[derive(Debug, Clone)]
+pub struct TierVerdict {
+    pub test_case: String,
+    pub tier: String,
+    pub overhead_pct: f64,
+    pub success_rate: f64,
+    pub passed: bool,
+}
+
+impl TierVerdict {
+    /// A tier fails if its CPU overhead exceeds `threshold_pct` or if either
+    /// side of the comparison had failing runs
+    pub fn evaluate(
+        test_case: &str,
+        tier: &str,
+        baseline: &ComprehensiveMetrics,
+        tested: &ComprehensiveMetrics,
+        threshold_pct: f64,
+    ) -> Self {
+        let overhead_pct = tested.overhead_vs(baseline);
+        let success_rate = baseline.success_rate.min(tested.success_rate);
+
+        Self {
+            test_case: test_case.to_string(),
+            tier: tier.to_string(),
+            overhead_pct,
+            success_rate,
+            passed: overhead_pct <= threshold_pct && success_rate >= 100.0,
+        }
+    }
+}
+
+/// Print all verdicts and return whether every tier passed
+pub fn print_verdict_summary(verdicts: &[TierVerdict], threshold_pct: f64) -> bool {
+    println!("\n{}", "=".repeat(90).bright_magenta().bold());
+    println!(
+        "{}",
+        format!("Regression Verdicts (threshold {:.2}%)", threshold_pct)
+            .bright_magenta()
+            .bold()
+    );
+    println!("{}", "=".repeat(90).bright_magenta().bold());
+    println!(
+        "{:<30} {:<30} {:>12} {:>10} {:>6}",
+        "Test Case".bold(),
+        "Tier".bold(),
+        "Overhead".bold(),
+        "Success".bold(),
+        "".bold()
+    );
+    println!("{}", "-".repeat(90));
+
+    for verdict in verdicts {
+        println!(
+            "{:<30} {:<30} {:>12} {:>10} {:>6}",
+            verdict.test_case,
+            verdict.tier,
+            format!("{:+.2}%", verdict.overhead_pct),
+            format!("{:.1}%", verdict.success_rate),
+            if verdict.passed {
+                "PASS".green()
+            } else {
+                "FAIL".red().bold()
+            }
+        );
+    }
+
+    let failed = verdicts.iter().filter(|v| !v.passed).count();
+    if failed == 0 {
+        println!("\n{}", "VERDICT: PASS".bright_green().bold());
+    } else {
+        println!(
+            "\n{}",
+            format!("VERDICT: FAIL ({} of {} tiers)", failed, verdicts.len())
+                .bright_red()
+                .bold()
+        );
+    }
+
+    failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comprehensive_benchmarking::MemoryStats;
+    use std::time::Duration;
+
+    fn metrics(micros: u64, successes: usize) -> ComprehensiveMetrics {
+        ComprehensiveMetrics::from_runs(vec![Duration::from_micros(micros); 4], MemoryStats::default(), successes)
+    }
+
+    #[test]
+    fn injected_regression_fails_the_summary() {
+        let baseline = metrics(100, 4);
+        let regressed = TierVerdict::evaluate("case", "Tier 1", &baseline, &metrics(200, 4), 50.0);
+        assert!(!regressed.passed);
+        assert!((regressed.overhead_pct - 100.0).abs() < 1e-9);
+
+        let within = TierVerdict::evaluate("case", "Tier 2", &baseline, &metrics(120, 4), 50.0);
+        assert!(within.passed);
+
+        assert!(print_verdict_summary(std::slice::from_ref(&within), 50.0));
+        assert!(!print_verdict_summary(&[within, regressed], 50.0));
+    }
+
+    #[test]
+    fn failing_runs_fail_the_tier_regardless_of_overhead() {
+        let verdict = TierVerdict::evaluate("case", "Tier 1", &metrics(100, 4), &metrics(100, 3), 50.0);
+        assert_eq!(verdict.success_rate, 75.0);
+        assert!(!verdict.passed);
+    }
+}
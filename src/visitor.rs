@@ -0,0 +1,137 @@
+use crate::graph::{DijkstraState, Graph, NodeId, QueueNode, ShortestPathResult};
+use std::collections::BinaryHeap;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+/// Callbacks invoked by `dijkstra_with_visitor` as the search progresses.
+/// Both hooks default to doing nothing, so a visitor implements only the
+/// ones it needs.
+pub trait DijkstraVisitor {
+    /// Called once per node as it is settled at its final `distance`, in
+    /// non-decreasing order of distance. `ControlFlow::Break` stops the
+    /// search immediately.
+    fn on_settle(&mut self, _node: NodeId, _distance: u32) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called whenever an edge `from -> to` improves the tentative distance
+    /// of `to` to `new_distance`. Equal-distance ties are not reported.
+    fn on_relax(&mut self, _from: NodeId, _to: NodeId, _new_distance: u32) {}
+}
+
+/// Tier 1 baseline with `visitor` hooked into every settle and relaxation.
+/// If the visitor stops the search before `target` is settled, the result
+/// has distance `None` and an empty path, as for an unreachable target.
+pub fn dijkstra_with_visitor(
+    graph: Arc<Graph>,
+    source: NodeId,
+    target: NodeId,
+    visitor: &mut dyn DijkstraVisitor,
+) -> ShortestPathResult {
+    let mut state = DijkstraState::new(graph.nodes, source);
+    let mut queue = BinaryHeap::new();
+
+    queue.push(QueueNode {
+        node: source,
+        distance: 0,
+    });
+
+    while let Some(QueueNode { node, distance }) = queue.pop() {
+        if state.visited[node.0] || distance > state.distances[node.0] {
+            continue;
+        }
+
+        state.visited[node.0] = true;
+        state.nodes_expanded += 1;
+
+        if visitor.on_settle(node, distance).is_break() || node == target {
+            break;
+        }
+
+        for edge in &graph.adjacency_list[node.0] {
+            let new_distance = distance.saturating_add(edge.weight);
+
+            if new_distance < state.distances[edge.to.0] {
+                state.distances[edge.to.0] = new_distance;
+                state.predecessors[edge.to.0] = Some(node);
+                visitor.on_relax(node, edge.to, new_distance);
+
+                queue.push(QueueNode {
+                    node: edge.to,
+                    distance: new_distance,
+                });
+            } else if new_distance == state.distances[edge.to.0] {
+                state.break_tie(node, edge.to);
+            }
+        }
+    }
+
+    let mut result = ShortestPathResult::reconstruct_path(&state, source, target);
+    if !state.visited.get(target.0).copied().unwrap_or(false) {
+        // Stopped early: any distance recorded for the target is tentative
+        result.distance = None;
+        result.path.clear();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tier_baselines::dijkstra_tier1_baseline;
+
+    /// Records settled nodes, stopping after `limit` of them
+    struct SettleOrder {
+        settled: Vec<(NodeId, u32)>,
+        relaxations: usize,
+        limit: usize,
+    }
+
+    impl DijkstraVisitor for SettleOrder {
+        fn on_settle(&mut self, node: NodeId, distance: u32) -> ControlFlow<()> {
+            self.settled.push((node, distance));
+            if self.settled.len() >= self.limit {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+
+        fn on_relax(&mut self, _from: NodeId, _to: NodeId, _new_distance: u32) {
+            self.relaxations += 1;
+        }
+    }
+
+    #[test]
+    fn nodes_settle_in_non_decreasing_distance() {
+        let graph = Arc::new(Graph::random_connected_seeded(300, 1_200, 100, 12));
+        let mut visitor = SettleOrder {
+            settled: Vec::new(),
+            relaxations: 0,
+            limit: usize::MAX,
+        };
+        let result = dijkstra_with_visitor(graph.clone(), NodeId(0), NodeId(299), &mut visitor);
+
+        assert!(visitor.settled.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert_eq!(visitor.settled[0], (NodeId(0), 0));
+        assert_eq!(visitor.settled.last(), Some(&(NodeId(299), result.distance.unwrap())));
+        assert!(visitor.relaxations >= visitor.settled.len() - 1);
+        assert_eq!(result.distance, dijkstra_tier1_baseline(graph, NodeId(0), NodeId(299)).distance);
+    }
+
+    #[test]
+    fn breaking_from_on_settle_stops_the_search() {
+        let graph = Arc::new(Graph::random_connected_seeded(300, 1_200, 100, 12));
+        let mut visitor = SettleOrder {
+            settled: Vec::new(),
+            relaxations: 0,
+            limit: 5,
+        };
+        let result = dijkstra_with_visitor(graph, NodeId(0), NodeId(299), &mut visitor);
+
+        assert_eq!(visitor.settled.len(), 5);
+        assert_eq!(result.nodes_expanded, 5);
+        assert_eq!(result.distance, None);
+        assert!(result.path.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::graph::{Graph, NodeId, SimpleRng, DEFAULT_SEED};
+
+/// How query endpoints are chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryBias {
+    /// Source and target drawn uniformly at random
+    Uniform,
+    /// Target drawn with probability proportional to the square of its hop
+    /// distance from the source, so long paths dominate the workload
+    LongPaths,
+}
+
+impl QueryBias {
+    pub const ALL: [QueryBias; 2] = [QueryBias::Uniform, QueryBias::LongPaths];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            QueryBias::Uniform => "Uniform",
+            QueryBias::LongPaths => "Long paths",
+        }
+    }
+}
+
+/// Generate `count` reproducible (source, target) query pairs.
+/// `Uniform` targets are any node other than the source; `LongPaths`
+/// targets are additionally guaranteed to be reachable from it.
+pub fn generate_query_workload(graph: &Graph, count: usize, bias: QueryBias) -> Vec<(NodeId, NodeId)> {
+    let mut rng = SimpleRng::new(DEFAULT_SEED);
+    let mut queries = Vec::with_capacity(count);
+
+    if graph.nodes == 0 {
+        return queries;
+    }
+
+    while queries.len() < count {
+        let source = NodeId(rng.next_usize() % graph.nodes);
+        let target = match bias {
+            QueryBias::Uniform => uniform_target(graph, source, &mut rng),
+            QueryBias::LongPaths => distance_weighted_target(graph, source, &mut rng),
+        };
+        queries.push((source, target));
+    }
+
+    queries
+}
+
+/// Mean hop distance between the endpoints of the reachable `queries`;
+/// 0.0 when none are reachable
+pub fn average_hops(graph: &Graph, queries: &[(NodeId, NodeId)]) -> f64 {
+    let hops: Vec<usize> = queries
+        .iter()
+        .map(|&(source, target)| graph.hop_distances(source)[target.0])
+        .filter(|&h| h != usize::MAX)
+        .collect();
+    if hops.is_empty() {
+        return 0.0;
+    }
+    hops.iter().sum::<usize>() as f64 / hops.len() as f64
+}
+
+fn uniform_target(graph: &Graph, source: NodeId, rng: &mut SimpleRng) -> NodeId {
+    if graph.nodes == 1 {
+        return source;
+    }
+    // Draw from the other nodes so the pair is never trivial
+    let offset = 1 + rng.next_usize() % (graph.nodes - 1);
+    NodeId((source.0 + offset) % graph.nodes)
+}
+
+fn distance_weighted_target(graph: &Graph, source: NodeId, rng: &mut SimpleRng) -> NodeId {
+    let hops = graph.hop_distances(source);
+    let weight = |h: usize| if h == usize::MAX { 0 } else { h * h };
+    let total: usize = hops.iter().map(|&h| weight(h)).sum();
+    if total == 0 {
+        return source;
+    }
+
+    // Unreachable nodes and the source itself (0 hops) carry no weight
+    let mut pick = rng.next_usize() % total;
+    for (node, &h) in hops.iter().enumerate() {
+        let w = weight(h);
+        if pick < w {
+            return NodeId(node);
+        }
+        pick -= w;
+    }
+
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_paths_bias_picks_more_distant_targets() {
+        let graph = Graph::random_connected(300, 900, 100);
+        let uniform = generate_query_workload(&graph, 200, QueryBias::Uniform);
+        let long = generate_query_workload(&graph, 200, QueryBias::LongPaths);
+
+        assert_eq!(uniform.len(), 200);
+        assert_eq!(long.len(), 200);
+        assert!(
+            average_hops(&graph, &long) > average_hops(&graph, &uniform),
+            "long paths {:.2} hops, uniform {:.2} hops",
+            average_hops(&graph, &long),
+            average_hops(&graph, &uniform)
+        );
+    }
+
+    #[test]
+    fn workloads_are_reproducible_and_never_trivial() {
+        let graph = Graph::random_connected(50, 150, 100);
+        for bias in QueryBias::ALL {
+            let queries = generate_query_workload(&graph, 40, bias);
+            assert_eq!(queries, generate_query_workload(&graph, 40, bias));
+            assert!(queries.iter().all(|(source, target)| source != target), "{}", bias.label());
+        }
+    }
+
+    #[test]
+    fn empty_graph_yields_no_queries() {
+        let graph = Graph::new(0);
+        assert!(generate_query_workload(&graph, 10, QueryBias::LongPaths).is_empty());
+    }
+}
@@ -0,0 +1,17 @@
+use std::process::Command;
+
+/// Seeds checked when `CROSS_CHECK_SEEDS` is unset; about 3 s in a debug build
+const DEFAULT_SEEDS: &str = "250";
+
+#[test]
+fn every_implementation_agrees_across_seeds() {
+    let seeds = std::env::var("CROSS_CHECK_SEEDS").unwrap_or_else(|_| DEFAULT_SEEDS.to_string());
+    let output = Command::new(env!("CARGO_BIN_EXE_dijkstra_eventchains"))
+        .args(["--cross-check", &seeds])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}{}", stdout, String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("All implementations agree"), "{}", stdout);
+}
@@ -0,0 +1,34 @@
+use std::process::{Command, Output};
+
+/// Benchmarks one small graph, failing on regressions past `threshold` percent
+fn run_with_threshold(threshold: &str) -> Output {
+    let config = std::env::temp_dir().join(format!(
+        "fail-on-regression-{}-{}.json",
+        std::process::id(),
+        threshold
+    ));
+    std::fs::write(&config, r#"[{"nodes": 20, "edges": 60, "runs": 3, "warmup": 0}]"#).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dijkstra_eventchains"))
+        .arg("--config")
+        .arg(&config)
+        .args(["--fail-on-regression", threshold])
+        .output()
+        .unwrap();
+    std::fs::remove_file(&config).unwrap();
+    output
+}
+
+#[test]
+fn any_overhead_fails_a_zero_threshold() {
+    let output = run_with_threshold("0");
+    assert_eq!(output.status.code(), Some(1), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("VERDICT: FAIL"));
+}
+
+#[test]
+fn overhead_within_a_large_threshold_passes() {
+    let output = run_with_threshold("1000000");
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("VERDICT: PASS"));
+}
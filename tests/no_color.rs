@@ -0,0 +1,44 @@
+use std::process::Command;
+
+/// Full report for one small graph, with colors forced on unless the
+/// arguments or `no_color_env` turn them off
+fn report(args: &[&str], no_color_env: bool) -> String {
+    let config = std::env::temp_dir().join(format!(
+        "no-color-{}-{}-{}.json",
+        std::process::id(),
+        args.len(),
+        no_color_env
+    ));
+    std::fs::write(&config, r#"[{"nodes": 20, "edges": 60, "runs": 3, "warmup": 0}]"#).unwrap();
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_dijkstra_eventchains"));
+    command.arg("--config").arg(&config).args(args);
+    command.env("CLICOLOR_FORCE", "1").env_remove("NO_COLOR");
+    if no_color_env {
+        command.env("NO_COLOR", "1");
+    }
+    let output = command.output().unwrap();
+    std::fs::remove_file(&config).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn reports_are_colored_when_forced() {
+    assert!(report(&[], false).contains("\x1b["));
+}
+
+#[test]
+fn no_color_flag_strips_every_escape_sequence() {
+    let stdout = report(&["--no-color"], false);
+    assert!(stdout.contains("Overhead"), "{}", stdout);
+    assert!(!stdout.contains("\x1b["), "{:?}", stdout);
+}
+
+#[test]
+fn no_color_variable_strips_every_escape_sequence() {
+    let stdout = report(&[], true);
+    assert!(stdout.contains("Overhead"), "{}", stdout);
+    assert!(!stdout.contains("\x1b["), "{:?}", stdout);
+}